@@ -4,6 +4,12 @@ WECHAT_OPEN_ID=""
 # 小程序access_token
 WECHAT_ACCESS_TOKEN=""
 
+# 小程序手机号快速验证组件返回的code
+WECHAT_PHONE_CODE=""
+
+# 素材上传测试使用的图片文件路径
+WECHAT_MEDIA_PATH=""
+
 # 微信公众号或小程序id
 WECHAT_APP_ID=""
 # 秘钥
@@ -18,5 +24,9 @@ WECHAT_KEY_V3=""
 WECHAT_NOTIFY_URL=""
 # 退款回调地址
 WECHAT_REFUND_NOTIFY_URL=""
+# 商户私钥文件(apiclient_key.pem)，与WECHAT_PRIVATE_KEY二选一
+WECHAT_PRIVATE_KEY_PATH=""
+# 商户证书文件(apiclient_cert.pem)，配置后可以不填WECHAT_SERIAL_NO
+WECHAT_CERTIFICATE_PATH=""
 # 私钥
 WECHAT_PRIVATE_KEY="
\ No newline at end of file
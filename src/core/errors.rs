@@ -1,8 +1,12 @@
-use std::{num::ParseIntError, string::FromUtf8Error};
+use std::{
+    fmt::{Display, Formatter},
+    num::ParseIntError,
+    string::FromUtf8Error,
+};
 
 use base64::DecodeError;
 
-use crate::{auth::access_token::AccessTokenBuilderError, model::{AmountBuilderError, PayerBuilderError}, pay::{app, config::WechatV3PayConfigBuilderError, h5, jsapi::JsApiPayBuilderError, native, parse_encrypt::ParseEncryptBuilderError, pay_info}};
+use crate::{auth::{access_token::AccessTokenBuilderError, encrypted_data::EncryptedDataBuilderError, jsapi_ticket::JsapiTicketBuilderError, phone_number::PhoneNumberBuilderError, token_manager::AccessTokenManagerBuilderError}, message::{dispatcher::SubscribeDispatcherBuilderError, kf_account::KfAccountsBuilderError, CustomerServiceBuilderError, push::PushServerBuilderError, reply, template_message::TemplateMessageBuilderError}, model::{AmountBuilderError, PayerBuilderError}, pay::{app, config::WechatV3PayConfigBuilderError, h5, jsapi::JsApiPayBuilderError, native, parse_encrypt::ParseEncryptBuilderError, pay_info}, media::{MaterialBuilderError, TempMediaBuilderError}, menu::{CustomMenuBuilderError, MatchRuleBuilderError}, security::sec_check, template::{official::OfficialTemplatesBuilderError, PubTemplateTitlesBuilderError, TemplatesBuilderError}, wxa::{link, wxacode}};
 
 use super::request::RequestBuilderError;
 
@@ -27,8 +31,41 @@ pub enum RPayError {
     #[error("错误编码:{0},错误原因: {1}")]
     Error(String, String),
 
+    /// 接口返回的业务异常(非2xx状态码或errcode不为0)
+    #[error("接口调用异常[{provider}]: 状态码:{http_status},错误编码:{code},错误原因:{message}")]
+    Api {
+        /// 接口提供方
+        provider: ApiProvider,
+        /// http状态码
+        http_status: u16,
+        /// 错误编码，微信支付为字符串(如SYSTEM_ERROR)，公众平台为errcode(如40001)
+        code: String,
+        /// 错误原因
+        message: String,
+        /// 请求id，微信支付取响应头Request-ID，公众平台取errmsg中的rid
+        request_id: Option<String>,
+        /// 错误详情，微信支付返回的detail字段
+        detail: Option<serde_json::Value>,
+    },
+
     #[error("错误原因:{0}")]
     ErrorWithMsg(String),
+
+    /// 缺少配置项，参数为配置项名称(环境变量名或字段名)
+    #[error("缺少配置项: {0}")]
+    MissingConfig(String),
+
+    #[error("toml解析异常: {0}")]
+    TomlParseError(#[from] toml::de::Error),
+
+    #[error("redis异常: {0}")]
+    RedisError(#[from] redis::RedisError),
+
+    #[error("redis连接池异常: {0}")]
+    RedisPoolError(#[from] r2d2::Error),
+
+    #[error("xml解析异常: {0}")]
+    XmlError(#[from] quick_xml::Error),
     
     #[error("错误原因:{0}")]
     DecodeError(#[from] DecodeError),
@@ -78,6 +115,220 @@ pub enum RPayError {
     #[error("获取access_token异常: {0}")]
     AccessTokenBuilderError(#[from] AccessTokenBuilderError),
 
+    #[error("access_token管理器参数异常: {0}")]
+    AccessTokenManagerBuilderError(#[from] AccessTokenManagerBuilderError),
+
+    #[error("获取jsapi_ticket异常: {0}")]
+    JsapiTicketBuilderError(#[from] JsapiTicketBuilderError),
+
+    #[error("开放数据参数异常: {0}")]
+    EncryptedDataBuilderError(#[from] EncryptedDataBuilderError),
+
+    #[error("获取手机号参数异常: {0}")]
+    PhoneNumberBuilderError(#[from] PhoneNumberBuilderError),
+
+    #[error("小程序码参数异常: {0}")]
+    WxaCodeBuilderError(#[from] wxacode::WxaCodeBuilderError),
+
+    #[error("小程序码参数异常: {0}")]
+    GetWxaCodeBuilderError(#[from] wxacode::GetWxaCodeBuilderError),
+
+    #[error("小程序码参数异常: {0}")]
+    GetUnlimitedWxaCodeBuilderError(#[from] wxacode::GetUnlimitedWxaCodeBuilderError),
+
+    #[error("小程序二维码参数异常: {0}")]
+    CreateQrCodeBuilderError(#[from] wxacode::CreateQrCodeBuilderError),
+
+    #[error("小程序链接参数异常: {0}")]
+    WxaLinkBuilderError(#[from] link::WxaLinkBuilderError),
+
+    #[error("小程序链接参数异常: {0}")]
+    JumpWxaBuilderError(#[from] link::JumpWxaBuilderError),
+
+    #[error("URL Scheme参数异常: {0}")]
+    GenerateSchemeBuilderError(#[from] link::GenerateSchemeBuilderError),
+
+    #[error("URL Link参数异常: {0}")]
+    GenerateUrlLinkBuilderError(#[from] link::GenerateUrlLinkBuilderError),
+
+    #[error("Short Link参数异常: {0}")]
+    GenerateShortLinkBuilderError(#[from] link::GenerateShortLinkBuilderError),
+
+    #[error("内容安全检测参数异常: {0}")]
+    SecurityCheckBuilderError(#[from] sec_check::SecurityCheckBuilderError),
+
+    #[error("文本内容安全识别参数异常: {0}")]
+    MsgSecCheckBuilderError(#[from] sec_check::MsgSecCheckBuilderError),
+
+    #[error("音视频内容安全识别参数异常: {0}")]
+    MediaCheckAsyncBuilderError(#[from] sec_check::MediaCheckAsyncBuilderError),
+
+    #[error("消息推送参数异常: {0}")]
+    PushServerBuilderError(#[from] PushServerBuilderError),
+
+    #[error("被动回复参数异常: {0}")]
+    ReplyBuilderError(#[from] reply::ReplyBuilderError),
+
+    #[error("被动回复视频参数异常: {0}")]
+    VideoBuilderError(#[from] reply::VideoBuilderError),
+
+    #[error("被动回复音乐参数异常: {0}")]
+    MusicBuilderError(#[from] reply::MusicBuilderError),
+
+    #[error("被动回复图文参数异常: {0}")]
+    ArticleBuilderError(#[from] reply::ArticleBuilderError),
+
+    #[error("临时素材参数异常: {0}")]
+    TempMediaBuilderError(#[from] TempMediaBuilderError),
+
+    #[error("永久素材参数异常: {0}")]
+    MaterialBuilderError(#[from] MaterialBuilderError),
+
+    #[error("自定义菜单参数异常: {0}")]
+    CustomMenuBuilderError(#[from] CustomMenuBuilderError),
+
+    #[error("个性化菜单匹配规则参数异常: {0}")]
+    MatchRuleBuilderError(#[from] MatchRuleBuilderError),
+
+    #[error("客服消息参数异常: {0}")]
+    CustomerServiceBuilderError(#[from] CustomerServiceBuilderError),
+
+    #[error("客服帐号管理参数异常: {0}")]
+    KfAccountsBuilderError(#[from] KfAccountsBuilderError),
+
+    #[error("订阅消息发送器参数异常: {0}")]
+    SubscribeDispatcherBuilderError(#[from] SubscribeDispatcherBuilderError),
+
+    #[error("模板消息参数异常: {0}")]
+    TemplateMessageBuilderError(#[from] TemplateMessageBuilderError),
+
+    #[error("模板管理参数异常: {0}")]
+    OfficialTemplatesBuilderError(#[from] OfficialTemplatesBuilderError),
+
+    #[error("订阅消息模板参数异常: {0}")]
+    TemplatesBuilderError(#[from] TemplatesBuilderError),
+
+    #[error("公共模板查询参数异常: {0}")]
+    PubTemplateTitlesBuilderError(#[from] PubTemplateTitlesBuilderError),
+
     #[error("请求异常: {0}")]
     RequestBuilderError(#[from] RequestBuilderError),    
-}
\ No newline at end of file
+}
+
+impl RPayError {
+    /// 接口错误编码，非接口异常返回None
+    pub fn api_code(&self) -> Option<&str> {
+        match self {
+            RPayError::Api { code, .. } => Some(code),
+            _ => None,
+        }
+    }
+
+    /// 是否可以重试，根据错误码目录判断，网络超时和连接异常同样可以重试
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            RPayError::Api { provider, http_status, code, .. } => lookup_error_code(*provider, code)
+                .map(|info| info.retryable)
+                .unwrap_or(*http_status >= 500 || *http_status == 429),
+            RPayError::ReqwestError(err) => err.is_timeout() || err.is_connect(),
+            _ => false,
+        }
+    }
+
+    /// access_token是否已失效(40001/40014/42001)，需要刷新后重试
+    pub fn is_token_expired(&self) -> bool {
+        matches!(
+            self,
+            RPayError::Api { provider: ApiProvider::Wechat, code, .. } if TOKEN_EXPIRED_CODES.contains(&code.as_str())
+        )
+    }
+}
+
+/// 接口提供方
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum ApiProvider {
+    /// 微信支付 api.mch.weixin.qq.com
+    WechatPay,
+    /// 微信公众平台/小程序 api.weixin.qq.com
+    #[default]
+    Wechat,
+}
+
+impl Display for ApiProvider {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ApiProvider::WechatPay => write!(fmt, "wechat_pay"),
+            ApiProvider::Wechat => write!(fmt, "wechat"),
+        }
+    }
+}
+
+/// 错误码目录项
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ErrorCodeInfo {
+    /// 接口提供方
+    pub provider: ApiProvider,
+    /// 错误编码
+    pub code: &'static str,
+    /// 错误说明
+    pub description: &'static str,
+    /// 是否可以重试
+    pub retryable: bool,
+}
+
+/// access_token失效的错误码
+pub const TOKEN_EXPIRED_CODES: [&str; 3] = ["40001", "40014", "42001"];
+
+const fn code_info(
+    provider: ApiProvider,
+    code: &'static str,
+    retryable: bool,
+    description: &'static str,
+) -> ErrorCodeInfo {
+    ErrorCodeInfo { provider, code, description, retryable }
+}
+
+/// 已知错误码目录
+pub static ERROR_CODES: &[ErrorCodeInfo] = &[
+    code_info(ApiProvider::Wechat, "-1", true, "系统繁忙，请稍候再试"),
+    code_info(ApiProvider::Wechat, "40001", true, "access_token无效或已过期，刷新后重试"),
+    code_info(ApiProvider::Wechat, "40014", true, "不合法的access_token，刷新后重试"),
+    code_info(ApiProvider::Wechat, "42001", true, "access_token超时，刷新后重试"),
+    code_info(ApiProvider::Wechat, "45011", true, "API调用太频繁，请稍候再试"),
+    code_info(ApiProvider::Wechat, "40002", false, "不合法的凭证类型"),
+    code_info(ApiProvider::Wechat, "40013", false, "不合法的AppID"),
+    code_info(ApiProvider::Wechat, "40029", false, "code无效"),
+    code_info(ApiProvider::Wechat, "40125", false, "AppSecret无效"),
+    code_info(ApiProvider::Wechat, "40163", false, "code已被使用"),
+    code_info(ApiProvider::Wechat, "40164", false, "调用接口的IP地址不在白名单中"),
+    code_info(ApiProvider::Wechat, "40243", false, "AppSecret已被冻结"),
+    code_info(ApiProvider::Wechat, "41030", false, "page路径不正确"),
+    code_info(ApiProvider::Wechat, "43101", false, "用户拒绝接受消息"),
+    code_info(ApiProvider::Wechat, "45009", false, "接口调用超过每日限额"),
+    code_info(ApiProvider::Wechat, "47003", false, "模板参数不准确"),
+    code_info(ApiProvider::Wechat, "89503", false, "此IP调用需要管理员确认"),
+    code_info(ApiProvider::WechatPay, "SYSTEM_ERROR", true, "系统异常，请用相同参数重新调用"),
+    code_info(ApiProvider::WechatPay, "FREQUENCY_LIMITED", true, "频率超限，请降低请求接口频率"),
+    code_info(ApiProvider::WechatPay, "BANK_ERROR", true, "银行系统异常，请用相同参数重新调用"),
+    code_info(ApiProvider::WechatPay, "NOTENOUGH", false, "余额不足"),
+    code_info(ApiProvider::WechatPay, "ORDERPAID", false, "订单已支付"),
+    code_info(ApiProvider::WechatPay, "ORDER_CLOSED", false, "订单已关闭"),
+    code_info(ApiProvider::WechatPay, "OUT_TRADE_NO_USED", false, "商户订单号重复"),
+    code_info(ApiProvider::WechatPay, "PARAM_ERROR", false, "参数错误"),
+    code_info(ApiProvider::WechatPay, "INVALID_REQUEST", false, "请求参数符合参数格式，但不符合业务规则"),
+    code_info(ApiProvider::WechatPay, "SIGN_ERROR", false, "签名错误"),
+    code_info(ApiProvider::WechatPay, "NO_AUTH", false, "商户无权限"),
+    code_info(ApiProvider::WechatPay, "APPID_MCHID_NOT_MATCH", false, "AppID和mch_id不匹配"),
+    code_info(ApiProvider::WechatPay, "MCH_NOT_EXISTS", false, "商户号不存在"),
+    code_info(ApiProvider::WechatPay, "RESOURCE_NOT_EXISTS", false, "订单不存在"),
+    code_info(ApiProvider::WechatPay, "RULE_LIMIT", false, "业务规则限制"),
+    code_info(ApiProvider::WechatPay, "ACCOUNT_ERROR", false, "账号异常"),
+    code_info(ApiProvider::WechatPay, "USER_ACCOUNT_ABNORMAL", false, "退款请求失败，用户账号已注销"),
+];
+
+/// 根据接口提供方和错误编码查询错误码目录
+pub fn lookup_error_code(provider: ApiProvider, code: &str) -> Option<&'static ErrorCodeInfo> {
+    ERROR_CODES
+        .iter()
+        .find(|info| info.provider == provider && info.code == code)
+}
@@ -1,4 +1,5 @@
 pub mod errors;
 pub mod common;
 pub mod request;
+pub mod response;
 pub mod utils;
\ No newline at end of file
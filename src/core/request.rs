@@ -1,12 +1,16 @@
 use crate::common::HttpMethod;
-use crate::constant::{ACCEPT, AUTHORIZATION};
+use crate::constant::{ACCEPT, AUTHORIZATION, REQUEST_ID};
+use crate::core::errors::ApiProvider;
+use crate::core::response::ApiResponse;
 use crate::pay::config::WechatV3PayConfig;
-use crate::{utils, RPayError, RPayResult};
+use crate::{RPayError, RPayResult};
 use derive_builder::Builder;
 use reqwest::header::{HeaderMap, CONTENT_TYPE, USER_AGENT};
-use reqwest::Client;
+use bytes::Bytes;
+use reqwest::{multipart::Form, Client, Method, StatusCode};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
@@ -22,35 +26,74 @@ pub struct Request {
     #[builder(default, setter(into))]
     #[serde(skip)]
     method: HttpMethod,
+    /// 接口提供方，决定响应中错误信息的解析方式
+    #[builder(default, setter(into))]
+    #[serde(skip)]
+    provider: ApiProvider,
 }
 
 impl Request {
-    /// 构建请求
+    /// 构建微信支付请求
     pub async fn build_pay_request<T: DeserializeOwned>(
         wechat_sdk: WechatV3PayConfig,
         method: HttpMethod,
         url: &str,
         body: String,
     ) -> RPayResult<T> {
-        let headers = Self::build_header(&wechat_sdk, method.clone(), url, body.clone())?;
-        let client = Client::new();
-        let url = format!("https://api.mch.weixin.qq.com{}", url);
-        println!("请求url: {:?}", url);
-        let builder = match method {
-            HttpMethod::GET => client.get(url),
-            HttpMethod::POST => client.post(url),
-            HttpMethod::PUT => client.put(url),
-            HttpMethod::DELETE => client.delete(url),
-            HttpMethod::PATCH => client.patch(url),
-        };
-        builder
+        Self::build_pay_request_with_response(wechat_sdk, method, url, body)
+            .await
+            .map(ApiResponse::into_body)
+    }
+
+    /// 构建微信支付请求(同步)
+    #[cfg(feature = "blocking")]
+    pub fn build_pay_request_blocking<T: DeserializeOwned>(
+        wechat_sdk: WechatV3PayConfig,
+        method: HttpMethod,
+        url: &str,
+        body: String,
+    ) -> RPayResult<T> {
+        Self::build_pay_request_with_response_blocking(wechat_sdk, method, url, body).map(ApiResponse::into_body)
+    }
+
+    /// 构建微信支付请求，返回包含状态码、响应头(Request-ID)和原始响应体的响应信封
+    pub async fn build_pay_request_with_response<T: DeserializeOwned>(
+        wechat_sdk: WechatV3PayConfig,
+        method: HttpMethod,
+        url: &str,
+        body: String,
+    ) -> RPayResult<ApiResponse<T>> {
+        Self::pay_request(&wechat_sdk, method, url, body)?
+            .send_with_response()
+            .await
+    }
+
+    /// 构建微信支付请求(同步)，返回响应信封
+    #[cfg(feature = "blocking")]
+    pub fn build_pay_request_with_response_blocking<T: DeserializeOwned>(
+        wechat_sdk: WechatV3PayConfig,
+        method: HttpMethod,
+        url: &str,
+        body: String,
+    ) -> RPayResult<ApiResponse<T>> {
+        Self::pay_request(&wechat_sdk, method, url, body)?.send_with_response_blocking()
+    }
+
+    // 构建微信支付请求
+    fn pay_request(
+        wechat_sdk: &WechatV3PayConfig,
+        method: HttpMethod,
+        url: &str,
+        body: String,
+    ) -> RPayResult<Request> {
+        let headers = Self::build_header(wechat_sdk, method.clone(), url, body.clone())?;
+        Ok(RequestBuilder::default()
+            .url(format!("https://api.mch.weixin.qq.com{}", url))
             .headers(headers)
             .body(body)
-            .send()
-            .await?
-            .json::<T>()
-            .await
-            .map(Ok)?
+            .method(method)
+            .provider(ApiProvider::WechatPay)
+            .build()?)
     }
 
     // 构建请求头信息
@@ -71,8 +114,12 @@ impl Request {
             "{}\n{}\n{}\n{}\n{}\n",
             method, url, timestamp, nonce_str, body,
         );
-        let signature = utils::sha256_sign(sdk.private_key.clone().unwrap_or_default(), signature)?;
-        let authorization = format!("WECHATPAY2-SHA256-RSA2048 mchid=\"{mch_id}\",nonce_str=\"{nonce_str}\",signature=\"{signature}\",timestamp=\"{timestamp}\",serial_no=\"{serial_no}\"");
+        let signer = sdk.signer()?;
+        let schema = signer.algorithm().auth_schema().ok_or_else(|| {
+            RPayError::ErrorWithMsg(format!("V3接口不支持{}签名", signer.algorithm()))
+        })?;
+        let signature = signer.sign(signature.as_bytes())?;
+        let authorization = format!("{schema} mchid=\"{mch_id}\",nonce_str=\"{nonce_str}\",signature=\"{signature}\",timestamp=\"{timestamp}\",serial_no=\"{serial_no}\"");
         let mut headers = HeaderMap::new();
         headers.insert(ACCEPT, "application/json".parse().unwrap());
         let chrome_agent = "Mozilla/5.0 (Linux; Android 10; Redmi K30 Pro) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/86.0.4240.198 Mobile Safari/537.36";
@@ -100,21 +147,415 @@ impl Request {
 
     /// 构建请求
     pub async fn send<T: DeserializeOwned>(&self) -> RPayResult<T> {
-        println!("请求url: {:?}", &self.url);
-        let client = Client::new();
-        let mut builder = match self.method {
-            HttpMethod::GET => client.get(&self.url),
-            HttpMethod::POST => client.post(&self.url),
-            HttpMethod::PUT => client.put(&self.url),
-            HttpMethod::DELETE => client.delete(&self.url),
-            HttpMethod::PATCH => client.patch(&self.url),
-        };
+        self.send_with_response().await.map(ApiResponse::into_body)
+    }
+
+    /// 构建请求，返回包含状态码、响应头和原始响应体的响应信封
+    pub async fn send_with_response<T: DeserializeOwned>(&self) -> RPayResult<ApiResponse<T>> {
+        let (status, headers, raw) = self.execute().await?;
+        self.build_response(status, headers, raw)
+    }
+
+    /// 构建请求，成功时返回二进制响应体(如图片)，响应为JSON时按接口异常处理
+    pub async fn send_bytes(&self) -> RPayResult<ApiResponse<Bytes>> {
+        let (status, headers, raw) = self.execute().await?;
+        self.build_bytes_response(status, headers, raw)
+    }
+
+    /// 构建multipart/form-data请求，用于上传文件
+    pub async fn send_multipart<T: DeserializeOwned>(&self, form: Form) -> RPayResult<T> {
+        let (status, headers, raw) = self.execute_with(Some(form)).await?;
+        self.build_response(status, headers, raw).map(ApiResponse::into_body)
+    }
+
+    async fn execute(&self) -> RPayResult<(StatusCode, HeaderMap, Bytes)> {
+        self.execute_with(None).await
+    }
+
+    async fn execute_with(&self, form: Option<Form>) -> RPayResult<(StatusCode, HeaderMap, Bytes)> {
+        tracing::debug!("请求url: {}", self.redacted_url());
+        let mut builder = Client::new().request(Method::from(&self.method), &self.url);
+        if let Some(headers) = &self.headers {
+            builder = builder.headers(headers.clone())
+        }
+        if let Some(form) = form {
+            builder = builder.multipart(form)
+        } else if let Some(body) = &self.body {
+            builder = builder.body(body.to_string())
+        }
+        let resp = builder.send().await?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let raw = resp.bytes().await?;
+        Ok((status, headers, raw))
+    }
+
+    /// 构建请求(同步)
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking<T: DeserializeOwned>(&self) -> RPayResult<T> {
+        self.send_with_response_blocking().map(ApiResponse::into_body)
+    }
+
+    /// 构建请求(同步)，返回包含状态码、响应头和原始响应体的响应信封
+    #[cfg(feature = "blocking")]
+    pub fn send_with_response_blocking<T: DeserializeOwned>(&self) -> RPayResult<ApiResponse<T>> {
+        let (status, headers, raw) = self.execute_blocking()?;
+        self.build_response(status, headers, raw)
+    }
+
+    /// 构建请求(同步)，成功时返回二进制响应体，参见[`Request::send_bytes`]
+    #[cfg(feature = "blocking")]
+    pub fn send_bytes_blocking(&self) -> RPayResult<ApiResponse<Bytes>> {
+        let (status, headers, raw) = self.execute_blocking()?;
+        self.build_bytes_response(status, headers, raw)
+    }
+
+    /// 构建multipart/form-data请求(同步)，参见[`Request::send_multipart`]
+    #[cfg(feature = "blocking")]
+    pub fn send_multipart_blocking<T: DeserializeOwned>(&self, form: reqwest::blocking::multipart::Form) -> RPayResult<T> {
+        let (status, headers, raw) = self.execute_blocking_with(Some(form))?;
+        self.build_response(status, headers, raw).map(ApiResponse::into_body)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn execute_blocking(&self) -> RPayResult<(StatusCode, HeaderMap, Bytes)> {
+        self.execute_blocking_with(None)
+    }
+
+    #[cfg(feature = "blocking")]
+    fn execute_blocking_with(
+        &self,
+        form: Option<reqwest::blocking::multipart::Form>,
+    ) -> RPayResult<(StatusCode, HeaderMap, Bytes)> {
+        tracing::debug!("请求url: {}", self.redacted_url());
+        let mut builder = reqwest::blocking::Client::new().request(Method::from(&self.method), &self.url);
         if let Some(headers) = &self.headers {
             builder = builder.headers(headers.clone())
         }
-        if let Some(body) = &self.body {
+        if let Some(form) = form {
+            builder = builder.multipart(form)
+        } else if let Some(body) = &self.body {
             builder = builder.body(body.to_string())
         }
-        builder.send().await?.json::<T>().await.map(Ok)?
+        let resp = builder.send()?;
+        let status = resp.status();
+        let headers = resp.headers().clone();
+        let raw = resp.bytes()?;
+        Ok((status, headers, raw))
+    }
+
+    // 请求地址的查询参数中可能包含access_token，日志中只输出路径
+    fn redacted_url(&self) -> &str {
+        self.url.split_once('?').map_or(self.url.as_str(), |(path, _)| path)
+    }
+
+    // 同步和异步请求共用的响应处理
+    fn build_response<T: DeserializeOwned>(
+        &self,
+        status: StatusCode,
+        headers: HeaderMap,
+        raw: Bytes,
+    ) -> RPayResult<ApiResponse<T>> {
+        let body = Self::parse_response(self.provider, status, &headers, &raw)?;
+        Ok(ApiResponse { status, headers, body, raw })
+    }
+
+    // 二进制响应，Content-Type为JSON或状态码非2xx时按接口异常处理
+    fn build_bytes_response(
+        &self,
+        status: StatusCode,
+        headers: HeaderMap,
+        raw: Bytes,
+    ) -> RPayResult<ApiResponse<Bytes>> {
+        let is_json = headers
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok())
+            .map(|v| v.contains("json") || v.starts_with("text/"))
+            .unwrap_or(false);
+        if is_json || !status.is_success() {
+            let value = serde_json::from_slice::<Value>(&raw).ok();
+            if let Some(err) = Self::api_error(self.provider, status, &headers, value.as_ref()) {
+                return Err(err);
+            }
+        }
+        Ok(ApiResponse { status, headers, body: raw.clone(), raw })
+    }
+
+    /// 解析响应，非2xx状态码或errcode不为0时返回RPayError::Api
+    pub(crate) fn parse_response<T: DeserializeOwned>(
+        provider: ApiProvider,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: &[u8],
+    ) -> RPayResult<T> {
+        let value = serde_json::from_slice::<Value>(body);
+        if let Some(err) = Self::api_error(provider, status, headers, value.as_ref().ok()) {
+            return Err(err);
+        }
+        Ok(serde_json::from_value(value?)?)
+    }
+
+    // 从响应中提取接口异常
+    fn api_error(
+        provider: ApiProvider,
+        status: StatusCode,
+        headers: &HeaderMap,
+        body: Option<&Value>,
+    ) -> Option<RPayError> {
+        let field = |name: &str| body.and_then(|v| v.get(name)).cloned();
+        let (code, message) = match provider {
+            // 支付接口只按状态码判断是否出错，成功响应的业务数据中也可能有code字段
+            ApiProvider::WechatPay if status.is_success() => return None,
+            ApiProvider::WechatPay => (
+                field("code").and_then(|v| v.as_str().map(String::from)),
+                field("message").and_then(|v| v.as_str().map(String::from)),
+            ),
+            ApiProvider::Wechat => (
+                field("errcode")
+                    .and_then(|v| v.as_i64())
+                    .filter(|code| *code != 0)
+                    .map(|code| code.to_string()),
+                field("errmsg").and_then(|v| v.as_str().map(String::from)),
+            ),
+        };
+        if status.is_success() && code.is_none() {
+            return None;
+        }
+        let message = message.unwrap_or_else(|| status.canonical_reason().unwrap_or_default().to_string());
+        let request_id = match headers.get(REQUEST_ID).and_then(|v| v.to_str().ok()) {
+            Some(id) => Some(id.to_string()),
+            // 公众平台的请求id在errmsg中，形如 "invalid credential rid: 6638f4a1-..."
+            None => message
+                .split_once("rid: ")
+                .map(|(_, rid)| rid.trim().to_string()),
+        };
+        Some(RPayError::Api {
+            provider,
+            http_status: status.as_u16(),
+            code: code.unwrap_or_else(|| status.as_u16().to_string()),
+            message,
+            request_id,
+            detail: field("detail"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::pay::{
+        config::WechatV3PayConfigBuilder,
+        signer::{SignAlgorithm, Signer},
+    };
+
+    // 模拟外部签名服务提供的SM2签名器
+    #[derive(Debug)]
+    struct Sm2Signer;
+
+    impl Signer for Sm2Signer {
+        fn algorithm(&self) -> SignAlgorithm {
+            SignAlgorithm::Sm2
+        }
+
+        fn sign(&self, _content: &[u8]) -> RPayResult<String> {
+            Ok(String::from("SM2_SIGNATURE"))
+        }
+    }
+
+    /// 测试使用自定义SM2签名器构建V3请求头
+    #[test]
+    fn test_build_header_with_sm2_signer() -> RPayResult<()> {
+        let config = WechatV3PayConfigBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .mch_id("1900000001")
+            .serial_no("SERIAL_NO")
+            .signer(Arc::new(Sm2Signer))
+            .build()?;
+        let headers = Request::build_header(&config, HttpMethod::POST, "/v3/pay/transactions/jsapi", "{}")?;
+        let authorization = headers.get(AUTHORIZATION).unwrap().to_str().unwrap();
+        assert!(authorization.starts_with("WECHATPAY2-SM2-WITH-SM3 mchid=\"1900000001\""));
+        assert!(authorization.contains("signature=\"SM2_SIGNATURE\""));
+        assert_eq!(SignAlgorithm::Sm2.sign_type(), "SM2");
+        Ok(())
+    }
+
+    // 本地HTTP服务，读取一个请求后返回指定的响应
+    #[cfg(feature = "blocking")]
+    fn serve(status: &'static str, content_type: &'static str, body: &'static str) -> String {
+        use std::io::{Read, Write};
+
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            // 读取完整的请求头和请求体
+            loop {
+                let n = stream.read(&mut buf).unwrap();
+                request.extend_from_slice(&buf[..n]);
+                let text = String::from_utf8_lossy(&request).to_string();
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let length = head
+                        .lines()
+                        .filter_map(|line| line.split_once(':'))
+                        .find(|(name, _)| name.eq_ignore_ascii_case("content-length"))
+                        .map(|(_, value)| value.trim().parse::<usize>().unwrap())
+                        .unwrap_or(0);
+                    if body.len() >= length {
+                        break;
+                    }
+                }
+                if n == 0 {
+                    break;
+                }
+            }
+            let response = format!(
+                "HTTP/1.1 {}\r\nContent-Type: {}\r\nRequest-ID: STUB-REQUEST-ID\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                content_type,
+                body.len(),
+                body
+            );
+            stream.write_all(response.as_bytes()).unwrap();
+        });
+        format!("http://{}/cgi-bin/test?access_token=ACCESS_TOKEN", addr)
+    }
+
+    fn headers(request_id: Option<&str>) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        if let Some(request_id) = request_id {
+            headers.insert(REQUEST_ID, request_id.parse().unwrap());
+        }
+        headers
+    }
+
+    /// 测试公众平台接口errcode不为0时返回接口异常，并从errmsg中提取rid
+    #[test]
+    fn test_parse_wechat_errcode() {
+        let body = br#"{"errcode":40001,"errmsg":"invalid credential, access_token is invalid or not latest rid: 6638f4a1-5e2ba3d4-5d35a5d5"}"#;
+        match Request::parse_response::<Value>(ApiProvider::Wechat, StatusCode::OK, &headers(None), body) {
+            Err(RPayError::Api { provider, http_status, code, request_id, .. }) => {
+                assert_eq!(provider, ApiProvider::Wechat);
+                assert_eq!(http_status, 200);
+                assert_eq!(code, "40001");
+                assert_eq!(request_id.as_deref(), Some("6638f4a1-5e2ba3d4-5d35a5d5"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // errcode为0时正常返回
+        let body = br#"{"errcode":0,"errmsg":"ok","msgid":1}"#;
+        let value: Value = Request::parse_response(ApiProvider::Wechat, StatusCode::OK, &headers(None), body).unwrap();
+        assert_eq!(value["msgid"], 1);
+    }
+
+    /// 测试微信支付接口非2xx状态码时解析code、message和detail
+    #[test]
+    fn test_parse_wechat_pay_error() {
+        let body = r#"{"code":"PARAM_ERROR","message":"参数错误","detail":{"field":"/amount/total","issue":"should be >= 1"}}"#;
+        let headers = headers(Some("08F0A2C5B40610A10318D1D6E4A006-0"));
+        match Request::parse_response::<Value>(ApiProvider::WechatPay, StatusCode::BAD_REQUEST, &headers, body.as_bytes()) {
+            Err(RPayError::Api { provider, http_status, code, message, request_id, detail }) => {
+                assert_eq!(provider, ApiProvider::WechatPay);
+                assert_eq!(http_status, 400);
+                assert_eq!(code, "PARAM_ERROR");
+                assert_eq!(message, "参数错误");
+                assert_eq!(request_id.as_deref(), Some("08F0A2C5B40610A10318D1D6E4A006-0"));
+                assert_eq!(detail.unwrap()["field"], "/amount/total");
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // 响应体不是JSON时使用状态码
+        match Request::parse_response::<Value>(ApiProvider::WechatPay, StatusCode::BAD_GATEWAY, &HeaderMap::new(), b"Bad Gateway") {
+            Err(RPayError::Api { code, message, detail, .. }) => {
+                assert_eq!(code, "502");
+                assert_eq!(message, "Bad Gateway");
+                assert!(detail.is_none());
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+
+        // 成功响应中的code字段不视为错误
+        let body = br#"{"code":"SUCCESS","message":"OK","out_trade_no":"T001"}"#;
+        let value = Request::parse_response::<Value>(ApiProvider::WechatPay, StatusCode::OK, &HeaderMap::new(), body).unwrap();
+        assert_eq!(value["out_trade_no"], "T001");
+    }
+
+    /// 测试响应头中的Request-ID优先于errmsg中的rid
+    #[test]
+    fn test_request_id_header_precedence() {
+        let body = br#"{"errcode":45009,"errmsg":"reach max api daily quota limit rid: 6638f4a1-5e2ba3d4-5d35a5d5"}"#;
+        match Request::parse_response::<Value>(ApiProvider::Wechat, StatusCode::OK, &headers(Some("HEADER-ID")), body) {
+            Err(RPayError::Api { code, request_id, .. }) => {
+                assert_eq!(code, "45009");
+                assert_eq!(request_id.as_deref(), Some("HEADER-ID"));
+            }
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    fn bytes_response(status: StatusCode, content_type: &str, body: &'static [u8]) -> RPayResult<ApiResponse<Bytes>> {
+        let request = RequestBuilder::default().url("https://api.weixin.qq.com/wxa/getwxacode").build()?;
+        let mut headers = HeaderMap::new();
+        headers.insert(CONTENT_TYPE, content_type.parse().unwrap());
+        request.build_bytes_response(status, headers, Bytes::from_static(body))
+    }
+
+    /// 测试二进制响应按Content-Type和状态码区分图片和接口异常
+    #[test]
+    fn test_build_bytes_response() -> RPayResult<()> {
+        // JSON响应为接口异常
+        let err = bytes_response(StatusCode::OK, "application/json; encoding=utf-8", br#"{"errcode":41030,"errmsg":"invalid page"}"#)
+            .unwrap_err();
+        assert_eq!(err.api_code(), Some("41030"));
+        // 部分接口异常时返回text/plain
+        let err = bytes_response(StatusCode::OK, "text/plain", br#"{"errcode":40001,"errmsg":"invalid credential"}"#).unwrap_err();
+        assert!(err.is_token_expired());
+        // text/plain但不是异常信息时原样返回
+        assert_eq!(bytes_response(StatusCode::OK, "text/plain", b"hello")?.body, Bytes::from_static(b"hello"));
+        // 非2xx状态码为接口异常
+        let err = bytes_response(StatusCode::INTERNAL_SERVER_ERROR, "image/jpeg", b"\xff\xd8\xff").unwrap_err();
+        assert_eq!(err.api_code(), Some("500"));
+        // 图片原样返回
+        let resp = bytes_response(StatusCode::OK, "image/jpeg", b"\xff\xd8\xff\xe0")?;
+        assert_eq!(resp.body, resp.raw);
+        assert_eq!(resp.body.as_ref(), b"\xff\xd8\xff\xe0");
+        Ok(())
+    }
+
+    /// 测试日志中的请求地址不包含查询参数
+    #[test]
+    fn test_redacted_url() {
+        let request = RequestBuilder::default()
+            .url("https://api.weixin.qq.com/cgi-bin/menu/get?access_token=ACCESS_TOKEN")
+            .build()
+            .unwrap();
+        assert_eq!(request.redacted_url(), "https://api.weixin.qq.com/cgi-bin/menu/get");
+    }
+
+    /// 测试同步请求的完整流程
+    #[cfg(feature = "blocking")]
+    #[test]
+    fn test_send_blocking() -> RPayResult<()> {
+        let url = serve("200 OK", "application/json", r#"{"errcode":0,"errmsg":"ok","value":1}"#);
+        let request = RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(r#"{"key":"value"}"#.to_string())
+            .build()?;
+        let resp: ApiResponse<Value> = request.send_with_response_blocking()?;
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.body["value"], 1);
+        assert_eq!(resp.request_id(), Some("STUB-REQUEST-ID"));
+
+        let url = serve("200 OK", "application/json", r#"{"errcode":40001,"errmsg":"invalid credential"}"#);
+        let err = RequestBuilder::default().url(url).build()?.send_blocking::<Value>().unwrap_err();
+        assert!(err.is_token_expired());
+        Ok(())
     }
 }
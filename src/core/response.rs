@@ -0,0 +1,45 @@
+use bytes::Bytes;
+use reqwest::header::HeaderMap;
+use reqwest::StatusCode;
+
+use crate::constant::REQUEST_ID;
+
+/// 接口响应信封，包含解析后的数据、状态码、响应头和原始响应体
+#[derive(Debug, Clone)]
+pub struct ApiResponse<T> {
+    /// http状态码
+    pub status: StatusCode,
+    /// 响应头
+    pub headers: HeaderMap,
+    /// 解析后的响应数据
+    pub body: T,
+    /// 原始响应体
+    pub raw: Bytes,
+}
+
+impl<T> ApiResponse<T> {
+    /// 微信支付响应头中的Request-ID，向微信支付客服反馈问题时需要提供
+    pub fn request_id(&self) -> Option<&str> {
+        self.headers.get(REQUEST_ID).and_then(|v| v.to_str().ok())
+    }
+
+    /// 原始响应体文本
+    pub fn raw_text(&self) -> String {
+        String::from_utf8_lossy(&self.raw).to_string()
+    }
+
+    /// 只保留解析后的响应数据
+    pub fn into_body(self) -> T {
+        self.body
+    }
+
+    /// 转换响应数据，保留状态码、响应头和原始响应体
+    pub fn map<U, F: FnOnce(T) -> U>(self, f: F) -> ApiResponse<U> {
+        ApiResponse {
+            status: self.status,
+            headers: self.headers,
+            body: f(self.body),
+            raw: self.raw,
+        }
+    }
+}
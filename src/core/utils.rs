@@ -3,9 +3,7 @@ use base64::{DecodeError, Engine};
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use crate::{utils, RPayError, RPayResult};
-use rsa::pkcs8::DecodePrivateKey;
-use rsa::sha2::Digest;
-use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use crate::pay::signer::{RsaSigner, Signer};
 
 // 获取字符串
 #[allow(unused)]
@@ -52,20 +50,8 @@ where
 /// 加密信息
 #[allow(unused)]
 pub fn sha256_sign(private_key: String, content: String) -> Result<String, RPayError> {
-    println!("sha256_sign => private_key, {}, content: {}", private_key, content);
-    match RsaPrivateKey::from_pkcs8_pem(&private_key) {
-        Ok(psk) => {
-            let hasher = rsa::sha2::Sha256::new()
-                .chain_update(content)
-                .finalize();
-            let padding = Pkcs1v15Sign::new::<rsa::sha2::Sha256>();
-            match  psk.sign(padding, &hasher) {
-                Ok(sn) => Ok(utils::base64_encode(sn)),
-                Err(_) => Err(RPayError::ErrorWithMsg(String::from("签名失败"))),
-            }            
-        }
-        Err(err) => Err(RPayError::ErrorWithMsg(format!("私钥解析失败:{:?}", err))),
-    }
+    // 每次调用都会解析私钥，频繁签名请使用 pay::signer::RsaSigner
+    RsaSigner::from_pem(private_key)?.sign(content.as_bytes())
 }
 
  /// 创建签名信息
@@ -24,6 +24,7 @@ mod wechat;
 #[cfg(feature = "wechat")]
 pub use wechat::*;
 
-pub use core::errors::RPayError;
+pub use core::errors::{lookup_error_code, ApiProvider, ErrorCodeInfo, RPayError, ERROR_CODES};
+pub use core::response::ApiResponse;
 pub type RPayResult<T, E = RPayError> = Result<T, E>;
 pub use reqwest::multipart::{Form, Part};
\ No newline at end of file
@@ -1,7 +1,17 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use crate::{core::request::RequestBuilder, RPayResult};
+use crate::{common::HttpMethod, core::request::{Request, RequestBuilder}, RPayResult};
+
+/// access_token获取方式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum TokenMode {
+    /// 普通access_token(/cgi-bin/token)，获取新令牌后旧令牌5分钟内失效
+    #[default]
+    Normal,
+    /// 稳定版access_token(/cgi-bin/stable_token)，有效期内重复获取返回相同令牌，不会使其他服务持有的令牌失效
+    Stable,
+}
 
 /// accessToken 登录授权
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
@@ -16,6 +26,14 @@ pub struct AccessToken {
     #[builder(setter(into))]
     pub secret: String,
     // 获取access_token填写client_credential
+    /// 获取方式，默认为普通access_token
+    #[serde(skip)]
+    #[builder(default)]
+    pub mode: TokenMode,
+    /// 稳定版接口是否强制刷新，强制刷新会使上一次获取的令牌失效，每天限用20次
+    #[serde(skip)]
+    #[builder(default)]
+    pub force_refresh: bool,
 }
 
 impl AccessToken {
@@ -31,17 +49,37 @@ impl AccessToken {
     /// 返回一个`RPayResult<Response>`，其中`Response`是请求的结果，包含访问令牌等信息。
     /// 如果请求失败，会返回错误信息。
     pub async fn request(&mut self) -> RPayResult<Response> {
-        // 根据appid和secret构造获取访问令牌的URL
-        let url = format!("https://api.weixin.qq.com/cgi-bin/token?grant_type=client_credential&appid={}&secret={}", self.app_id, self.secret);
         // 构建请求，发送到微信服务器，并等待响应
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?
-            .send::<Response>()
-            .await?;
+        let resp = self.build_request()?.send::<Response>().await?;
         // 返回响应结果
         Ok(resp)
     }
+
+    /// 同步请求微信服务器获取访问令牌，参见[`AccessToken::request`]
+    #[cfg(feature = "blocking")]
+    pub fn request_blocking(&mut self) -> RPayResult<Response> {
+        self.build_request()?.send_blocking::<Response>()
+    }
+
+    // 构建获取访问令牌的请求
+    fn build_request(&self) -> RPayResult<Request> {
+        if self.mode == TokenMode::Stable {
+            let body = serde_json::json!({
+                "grant_type": "client_credential",
+                "appid": self.app_id,
+                "secret": self.secret,
+                "force_refresh": self.force_refresh,
+            });
+            return Ok(RequestBuilder::default()
+                .url("https://api.weixin.qq.com/cgi-bin/stable_token")
+                .method(HttpMethod::POST)
+                .body(body.to_string())
+                .build()?);
+        }
+        // 根据appid和secret构造获取访问令牌的URL
+        let url = format!("https://api.weixin.qq.com/cgi-bin/token?grant_type=client_credential&appid={}&secret={}", self.app_id, self.secret);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
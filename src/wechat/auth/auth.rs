@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use crate::{
     core::{
         common::{GrantType, LoginType},
-        request::RequestBuilder,
+        request::{Request, RequestBuilder},
     },
     RPayResult,
 };
@@ -38,57 +38,90 @@ pub struct Auth {
 impl Auth {
     /// 公众号登录 https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
    pub async fn web_login(&self) -> RPayResult<WebLoginResponse>  {
-        let url = format!("https://api.weixin.qq.com/sns/oauth2/access_token?appid={}&secret={}&code={}&grant_type=authorization_code", self.app_id,self.secret, self.wx_code);
         // 构建请求，发送到微信服务器，并等待响应
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?
+        let resp = Self::get(self.web_login_url())?
             .send::<WebLoginResponse>()
             .await?;
         // 返回响应结果
         Ok(resp)
     }
 
+    /// 公众号登录(同步)
+    #[cfg(feature = "blocking")]
+    pub fn web_login_blocking(&self) -> RPayResult<WebLoginResponse> {
+        Self::get(self.web_login_url())?.send_blocking::<WebLoginResponse>()
+    }
+
     /// 刷新access_token
     /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
     pub async fn web_refresh_token(&self,refresh_token: &str) -> RPayResult<WebRefreshTokenResponse> {
-        let url = format!("https://api.weixin.qq.com/sns/oauth2/refresh_token?appid={}&grant_type=refresh_token&refresh_token={}", self.app_id,refresh_token);
         // 构建请求，发送到微信服务器，并等待响应
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?
+        let resp = Self::get(self.web_refresh_token_url(refresh_token))?
             .send::<WebRefreshTokenResponse>()
             .await?;
         // 返回响应结果
         Ok(resp)
     }
 
+    /// 刷新access_token(同步)
+    #[cfg(feature = "blocking")]
+    pub fn web_refresh_token_blocking(&self, refresh_token: &str) -> RPayResult<WebRefreshTokenResponse> {
+        Self::get(self.web_refresh_token_url(refresh_token))?.send_blocking::<WebRefreshTokenResponse>()
+    }
+
     /// 公众号登录之后获取的登录用户信息
     /// https://developers.weixin.qq.com/doc/offiaccount/OA_Web_Apps/Wechat_webpage_authorization.html
     pub async fn web_sn_sapi_user_info(&self,access_token: String, openid: &str) -> RPayResult<WeLoginUserResponse> {
-        let url = format!("https://api.weixin.qq.com/sns/userinfo?access_token={}&openid={}&lang=zh_CN", access_token, openid);
         // 构建请求，发送到微信服务器，并等待响应
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?
+        let resp = Self::get(Self::web_user_info_url(&access_token, openid))?
             .send::<WeLoginUserResponse>()
             .await?;
         // 返回响应结果
         Ok(resp)
     }
 
+    /// 公众号登录之后获取的登录用户信息(同步)
+    #[cfg(feature = "blocking")]
+    pub fn web_sn_sapi_user_info_blocking(&self, access_token: String, openid: &str) -> RPayResult<WeLoginUserResponse> {
+        Self::get(Self::web_user_info_url(&access_token, openid))?.send_blocking::<WeLoginUserResponse>()
+    }
+
     /// 小程序登录  https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-login/code2Session.html
     pub async fn mini_program_login(&self) -> RPayResult<MiniProgramResponse> {
-        let url = format!("https://api.weixin.qq.com/sns/jscode2session?appid={}&secret={}&js_code={}&grant_type=authorization_code", self.app_id,self.secret, self.wx_code);
         // 构建请求，发送到微信服务器，并等待响应
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?
+        let resp = Self::get(self.mini_program_login_url())?
             .send::<MiniProgramResponse>()
             .await?;
         // 返回响应结果
         Ok(resp)
     }
+
+    /// 小程序登录(同步)
+    #[cfg(feature = "blocking")]
+    pub fn mini_program_login_blocking(&self) -> RPayResult<MiniProgramResponse> {
+        Self::get(self.mini_program_login_url())?.send_blocking::<MiniProgramResponse>()
+    }
+
+    fn web_login_url(&self) -> String {
+        format!("https://api.weixin.qq.com/sns/oauth2/access_token?appid={}&secret={}&code={}&grant_type=authorization_code", self.app_id,self.secret, self.wx_code)
+    }
+
+    fn web_refresh_token_url(&self, refresh_token: &str) -> String {
+        format!("https://api.weixin.qq.com/sns/oauth2/refresh_token?appid={}&grant_type=refresh_token&refresh_token={}", self.app_id,refresh_token)
+    }
+
+    fn web_user_info_url(access_token: &str, openid: &str) -> String {
+        format!("https://api.weixin.qq.com/sns/userinfo?access_token={}&openid={}&lang=zh_CN", access_token, openid)
+    }
+
+    fn mini_program_login_url(&self) -> String {
+        format!("https://api.weixin.qq.com/sns/jscode2session?appid={}&secret={}&js_code={}&grant_type=authorization_code", self.app_id,self.secret, self.wx_code)
+    }
+
+    // 构建GET请求
+    fn get(url: String) -> RPayResult<Request> {
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
 }
 
 /// 公众号登录之后获取的登录用户信息
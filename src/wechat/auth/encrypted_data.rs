@@ -0,0 +1,158 @@
+use aes::Aes128;
+use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
+use derive_builder::Builder;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{utils, RPayError, RPayResult};
+
+type Aes128Cbc = Cbc<Aes128, Pkcs7>;
+
+/// 小程序开放数据(encryptedData)，使用登录返回的session_key解密
+/// https://developers.weixin.qq.com/miniprogram/dev/framework/open-ability/signature.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct EncryptedData {
+    /// 包括敏感数据在内的完整用户信息的加密数据
+    #[serde(rename = "encryptedData")]
+    #[builder(setter(into))]
+    pub encrypted_data: String,
+    /// 加密算法的初始向量
+    #[builder(setter(into))]
+    pub iv: String,
+    /// 数据水印的最大有效期，单位：秒，默认与session_key有效期一致为7200，0表示不校验时间
+    #[serde(skip, default = "default_max_age")]
+    #[builder(default = "default_max_age()")]
+    pub max_age: i64,
+}
+
+fn default_max_age() -> i64 {
+    7200
+}
+
+impl EncryptedData {
+    /// 解密开放数据，并校验数据水印中的appid和时间戳
+    pub fn decrypt<T: DeserializeOwned>(&self, session_key: &str, app_id: &str) -> RPayResult<T> {
+        let plain_text = decrypt(session_key, &self.encrypted_data, &self.iv)?;
+        let value: Value = serde_json::from_slice(&plain_text)?;
+        let watermark: Watermark = value
+            .get("watermark")
+            .cloned()
+            .map(serde_json::from_value)
+            .transpose()?
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("解密数据缺少水印")))?;
+        watermark.validate(app_id, self.max_age)?;
+        Ok(serde_json::from_value(value)?)
+    }
+}
+
+/// AES-128-CBC解密，session_key、encrypted_data和iv均为base64编码
+pub fn decrypt(session_key: &str, encrypted_data: &str, iv: &str) -> RPayResult<Vec<u8>> {
+    let key = utils::base64_decode(session_key)?;
+    let iv = utils::base64_decode(iv)?;
+    let data = utils::base64_decode(encrypted_data)?;
+    let cipher = Aes128Cbc::new_from_slices(&key, &iv)
+        .map_err(|err| RPayError::ErrorWithMsg(format!("session_key或iv长度错误:{}", err)))?;
+    cipher
+        .decrypt_vec(&data)
+        .map_err(|err| RPayError::ErrorWithMsg(format!("开放数据解密失败:{}", err)))
+}
+
+/// 校验明文数据签名 signature = sha1(rawData + session_key)
+pub fn check_signature(raw_data: &str, session_key: &str, signature: &str) -> bool {
+    utils::sha1_hex(format!("{}{}", raw_data, session_key)).eq_ignore_ascii_case(signature)
+}
+
+/// 数据水印
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Watermark {
+    /// 小程序appid
+    pub appid: String,
+    /// 获取数据的时间戳，单位：秒
+    pub timestamp: i64,
+}
+
+impl Watermark {
+    /// 校验appid，max_age大于0时校验数据是否过期
+    pub fn validate(&self, app_id: &str, max_age: i64) -> RPayResult<()> {
+        if self.appid != app_id {
+            return Err(RPayError::ErrorWithMsg(format!("数据水印appid不匹配: {}", self.appid)));
+        }
+        if max_age > 0 && utils::get_timestamp()? / 1000 - self.timestamp > max_age {
+            return Err(RPayError::ErrorWithMsg(String::from("开放数据已过期")));
+        }
+        Ok(())
+    }
+}
+
+/// 用户信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UserInfo {
+    /// 用户唯一标识
+    #[serde(rename = "openId")]
+    pub open_id: Option<String>,
+    /// 用户昵称
+    #[serde(rename = "nickName")]
+    pub nick_name: Option<String>,
+    /// 性别 0:未知 1:男 2:女
+    pub gender: Option<i64>,
+    /// 城市
+    pub city: Option<String>,
+    /// 省份
+    pub province: Option<String>,
+    /// 国家
+    pub country: Option<String>,
+    /// 头像
+    #[serde(rename = "avatarUrl")]
+    pub avatar_url: Option<String>,
+    /// 用户在开放平台的唯一标识符
+    #[serde(rename = "unionId")]
+    pub union_id: Option<String>,
+    /// 数据水印
+    pub watermark: Watermark,
+}
+
+/// 手机号(旧版getPhoneNumber返回的加密数据)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PhoneNumberInfo {
+    /// 用户绑定的手机号（国外手机号会有区号）
+    #[serde(rename = "phoneNumber")]
+    pub phone_number: String,
+    /// 没有区号的手机号
+    #[serde(rename = "purePhoneNumber")]
+    pub pure_phone_number: String,
+    /// 区号
+    #[serde(rename = "countryCode")]
+    pub country_code: String,
+    /// 数据水印
+    pub watermark: Watermark,
+}
+
+/// 微信运动步数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WeRunData {
+    /// 最近30天的步数
+    #[serde(rename = "stepInfoList")]
+    pub step_info_list: Vec<StepInfo>,
+    /// 数据水印
+    pub watermark: Watermark,
+}
+
+/// 单日步数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StepInfo {
+    /// 时间戳，表示数据对应的时间
+    pub timestamp: i64,
+    /// 微信运动步数
+    pub step: i64,
+}
+
+/// 群分享信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShareInfo {
+    /// 群对当前小程序的唯一ID
+    #[serde(rename = "openGId")]
+    pub open_g_id: String,
+    /// 数据水印
+    pub watermark: Watermark,
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    core::request::{Request, RequestBuilder},
+    utils, RPayError, RPayResult,
+};
+
+use super::{token_manager::AccessTokenManager, token_store::CachedToken};
+
+/// jsapi_ticket 公众号调用JS-SDK的临时票据，有效期7200秒
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct JsapiTicket {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl JsapiTicket {
+    /// 获取jsapi_ticket
+    pub async fn request(&self) -> RPayResult<Response> {
+        self.build_request()?.send::<Response>().await
+    }
+
+    /// 同步获取jsapi_ticket，参见[`JsapiTicket::request`]
+    #[cfg(feature = "blocking")]
+    pub fn request_blocking(&self) -> RPayResult<Response> {
+        self.build_request()?.send_blocking::<Response>()
+    }
+
+    fn build_request(&self) -> RPayResult<Request> {
+        let url = format!(
+            "https://api.weixin.qq.com/cgi-bin/ticket/getticket?access_token={}&type=jsapi",
+            self.access_token
+        );
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    /// 错误码
+    pub errcode: Option<i64>,
+    /// 错误信息
+    pub errmsg: Option<String>,
+    /// 临时票据
+    pub ticket: Option<String>,
+    /// 有效期，单位：秒
+    pub expires_in: Option<i64>,
+}
+
+impl TryFrom<Response> for CachedToken {
+    type Error = RPayError;
+
+    fn try_from(resp: Response) -> Result<Self, Self::Error> {
+        let ticket = resp
+            .ticket
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("接口未返回jsapi_ticket")))?;
+        Ok(CachedToken::new(ticket, resp.expires_in.unwrap_or(7200)))
+    }
+}
+
+/// wx.config 注入的配置信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsSdkConfig {
+    /// 公众号的唯一标识
+    #[serde(rename = "appId")]
+    pub app_id: String,
+    /// 生成签名的时间戳，单位：秒
+    pub timestamp: i64,
+    /// 生成签名的随机串
+    #[serde(rename = "nonceStr")]
+    pub nonce_str: String,
+    /// 签名
+    pub signature: String,
+}
+
+impl JsSdkConfig {
+    /// 使用jsapi_ticket为当前网页生成wx.config配置
+    pub fn new(app_id: impl Into<String>, jsapi_ticket: &str, url: &str) -> RPayResult<Self> {
+        let timestamp = utils::get_timestamp()? / 1000;
+        let nonce_str = utils::get_nonce_str();
+        let signature = js_sdk_signature(jsapi_ticket, &nonce_str, timestamp, url);
+        Ok(Self {
+            app_id: app_id.into(),
+            timestamp,
+            nonce_str,
+            signature,
+        })
+    }
+}
+
+/// JS-SDK签名：参数按字段名排序后拼接，取SHA1，url为当前网页不包含#及其后面部分的完整地址
+pub fn js_sdk_signature(jsapi_ticket: &str, nonce_str: &str, timestamp: i64, url: &str) -> String {
+    let url = url.split('#').next().unwrap_or_default();
+    let content = format!(
+        "jsapi_ticket={}&noncestr={}&timestamp={}&url={}",
+        jsapi_ticket, nonce_str, timestamp, url
+    );
+    utils::sha1_hex(content)
+}
+
+/// jsapi_ticket管理器，与access_token使用相同的存储和刷新策略
+#[derive(Debug, Clone)]
+pub struct JsapiTicketManager {
+    tokens: AccessTokenManager,
+    refreshing: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl JsapiTicketManager {
+    /// 使用access_token管理器创建
+    pub fn new(tokens: AccessTokenManager) -> Self {
+        Self {
+            tokens,
+            refreshing: Arc::default(),
+        }
+    }
+
+    /// 获取jsapi_ticket，缓存中没有或即将过期时刷新
+    pub async fn get_ticket(&self) -> RPayResult<String> {
+        let key = self.tokens.cache_key("jsapi_ticket");
+        self.tokens
+            .cached(key, &self.refreshing, || async {
+                let resp = self
+                    .tokens
+                    .call(|access_token| async move {
+                        JsapiTicketBuilder::default().access_token(access_token).build()?.request().await
+                    })
+                    .await?;
+                CachedToken::try_from(resp)
+            })
+            .await
+    }
+
+    /// 同步获取jsapi_ticket，参见[`JsapiTicketManager::get_ticket`]
+    #[cfg(feature = "blocking")]
+    pub fn get_ticket_blocking(&self) -> RPayResult<String> {
+        let key = self.tokens.cache_key("jsapi_ticket");
+        self.tokens.cached_blocking(key, &self.refreshing, || {
+            let resp = self.tokens.call_blocking(|access_token| {
+                JsapiTicketBuilder::default().access_token(access_token).build()?.request_blocking()
+            })?;
+            CachedToken::try_from(resp)
+        })
+    }
+
+    /// 生成当前网页的wx.config配置
+    pub async fn js_sdk_config(&self, url: &str) -> RPayResult<JsSdkConfig> {
+        JsSdkConfig::new(self.tokens.app_id(), &self.get_ticket().await?, url)
+    }
+
+    /// 同步生成当前网页的wx.config配置
+    #[cfg(feature = "blocking")]
+    pub fn js_sdk_config_blocking(&self, url: &str) -> RPayResult<JsSdkConfig> {
+        JsSdkConfig::new(self.tokens.app_id(), &self.get_ticket_blocking()?, url)
+    }
+}
@@ -1,2 +1,7 @@
 pub mod access_token;
-pub mod auth;
\ No newline at end of file
+pub mod auth;
+pub mod token_store;
+pub mod token_manager;
+pub mod jsapi_ticket;
+pub mod encrypted_data;
+pub mod phone_number;
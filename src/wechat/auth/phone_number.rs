@@ -0,0 +1,83 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayError, RPayResult,
+};
+
+use super::{encrypted_data::PhoneNumberInfo, token_manager::AccessTokenManager};
+
+/// 小程序获取手机号，使用手机号快速验证组件返回的code换取
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/user-info/phone-number/getPhoneNumber.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct PhoneNumber {
+    /// 接口调用凭证，使用[`PhoneNumber::request_with`]时由令牌管理器提供
+    #[serde(skip)]
+    #[builder(default, setter(into))]
+    pub access_token: String,
+    /// 手机号获取凭证
+    #[builder(setter(into))]
+    pub code: String,
+}
+
+impl PhoneNumber {
+    /// 获取手机号
+    pub async fn request(&self) -> RPayResult<Response> {
+        self.build_request(&self.access_token)?.send::<Response>().await
+    }
+
+    /// 获取手机号(同步)
+    #[cfg(feature = "blocking")]
+    pub fn request_blocking(&self) -> RPayResult<Response> {
+        self.build_request(&self.access_token)?.send_blocking::<Response>()
+    }
+
+    /// 使用缓存的access_token获取手机号，令牌失效时自动刷新重试
+    pub async fn request_with(&self, tokens: &AccessTokenManager) -> RPayResult<PhoneNumberInfo> {
+        tokens
+            .call(|access_token| async move { self.build_request(&access_token)?.send::<Response>().await })
+            .await?
+            .into_phone_info()
+    }
+
+    /// 使用缓存的access_token获取手机号(同步)
+    #[cfg(feature = "blocking")]
+    pub fn request_with_blocking(&self, tokens: &AccessTokenManager) -> RPayResult<PhoneNumberInfo> {
+        tokens
+            .call_blocking(|access_token| self.build_request(&access_token)?.send_blocking::<Response>())?
+            .into_phone_info()
+    }
+
+    fn build_request(&self, access_token: &str) -> RPayResult<Request> {
+        let url = format!(
+            "https://api.weixin.qq.com/wxa/business/getuserphonenumber?access_token={}",
+            access_token
+        );
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(serde_json::to_string(self)?)
+            .build()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Response {
+    /// 错误码
+    pub errcode: Option<i64>,
+    /// 错误信息
+    pub errmsg: Option<String>,
+    /// 用户手机号信息
+    pub phone_info: Option<PhoneNumberInfo>,
+}
+
+impl Response {
+    /// 用户手机号信息
+    pub fn into_phone_info(self) -> RPayResult<PhoneNumberInfo> {
+        self.phone_info
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("接口未返回手机号信息")))
+    }
+}
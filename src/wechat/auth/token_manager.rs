@@ -0,0 +1,332 @@
+use std::{future::Future, sync::Arc, time::Duration};
+
+use derive_builder::Builder;
+
+use crate::{RPayError, RPayResult};
+
+use super::{
+    access_token::{AccessToken, AccessTokenBuilder, Response, TokenMode},
+    token_store::{CachedToken, MemoryTokenStore, TokenStore},
+};
+
+/// 访问令牌管理器
+///
+/// - 令牌缓存在[`TokenStore`]中，剩余有效期小于`refresh_ahead`时提前刷新
+/// - 同一进程内并发调用只会发起一次刷新请求
+/// - 多实例间通过存储的刷新锁保证同一时间只有一个实例刷新，其他实例继续使用旧令牌或等待刷新完成
+/// - 接口返回40001/40014/42001时通过[`AccessTokenManager::call`]使令牌失效并重试一次
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct AccessTokenManager {
+    /// 第三方用户唯一凭证
+    #[builder(setter(into))]
+    app_id: String,
+    /// 第三方用户唯一凭证密钥，即app secret
+    #[builder(setter(into))]
+    secret: String,
+    /// 获取方式，多个服务共用一个appid时使用[`TokenMode::Stable`]
+    #[builder(default)]
+    mode: TokenMode,
+    /// 令牌存储，默认为内存存储
+    #[builder(default = "Arc::new(MemoryTokenStore::new())")]
+    store: Arc<dyn TokenStore>,
+    /// 缓存key前缀
+    #[builder(default = "String::from(\"rpay\")", setter(into))]
+    key_prefix: String,
+    /// 提前刷新时间，单位：秒
+    #[builder(default = "300")]
+    refresh_ahead: i64,
+    /// 刷新锁超时时间，单位：秒
+    #[builder(default = "30")]
+    lock_ttl: u64,
+    /// 其他实例刷新时的等待间隔
+    #[builder(default = "Duration::from_millis(200)")]
+    wait_interval: Duration,
+    /// 其他实例刷新时的最大等待次数
+    #[builder(default = "50")]
+    wait_times: u32,
+    #[builder(setter(skip))]
+    refreshing: Arc<tokio::sync::Mutex<()>>,
+}
+
+impl AccessTokenManager {
+    /// 使用内存存储创建令牌管理器
+    pub fn new(app_id: impl Into<String>, secret: impl Into<String>) -> RPayResult<Self> {
+        Ok(AccessTokenManagerBuilder::default().app_id(app_id).secret(secret).build()?)
+    }
+
+    /// 获取访问令牌，缓存中没有或即将过期时刷新
+    pub async fn get_token(&self) -> RPayResult<String> {
+        self.cached(self.token_key(), &self.refreshing, || async {
+            self.fetch(self.access_token()?.request().await?)
+        })
+        .await
+    }
+
+    /// 同步获取访问令牌，参见[`AccessTokenManager::get_token`]，不能在异步运行时中调用
+    #[cfg(feature = "blocking")]
+    pub fn get_token_blocking(&self) -> RPayResult<String> {
+        self.cached_blocking(self.token_key(), &self.refreshing, || {
+            self.fetch(self.access_token()?.request_blocking()?)
+        })
+    }
+
+    /// 读取缓存的凭证，缓存中没有或即将过期时调用fetch刷新，refreshing用于合并同一进程内的并发刷新
+    pub(crate) async fn cached<F, Fut>(
+        &self,
+        key: String,
+        refreshing: &tokio::sync::Mutex<()>,
+        fetch: F,
+    ) -> RPayResult<String>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = RPayResult<CachedToken>>,
+    {
+        if let Some(token) = self.store_get(&key).await?.filter(|token| self.is_fresh(token)) {
+            return Ok(token.access_token);
+        }
+        let _guard = refreshing.lock().await;
+        // 等待锁期间可能已被其他调用刷新
+        let cached = self.store_get(&key).await?;
+        if let Some(token) = cached.clone().filter(|token| self.is_fresh(token)) {
+            return Ok(token.access_token);
+        }
+        let lock_key = format!("{}:lock", key);
+        let owner = uuid::Uuid::new_v4().to_string();
+        let (ttl, lock, value) = (self.lock_ttl, lock_key.clone(), owner.clone());
+        if self.store_op(move |store| store.try_lock(&lock, &value, ttl)).await? {
+            // 持有锁期间刷新并写入新凭证，最后再释放锁，避免其他实例在写入前重复刷新
+            let result: RPayResult<String> = async {
+                // 获取锁之前其他实例可能刚写入新凭证
+                if let Some(token) = self.store_get(&key).await?.filter(|token| self.is_fresh(token)) {
+                    return Ok(token.access_token);
+                }
+                let token = fetch().await?;
+                let access_token = token.access_token.clone();
+                let key = key.clone();
+                self.store_op(move |store| store.set(&key, &token)).await?;
+                Ok(access_token)
+            }
+            .await;
+            // 锁会在ttl后自动过期，释放失败不影响已获取的凭证
+            if let Err(err) = self.store_op(move |store| store.unlock(&lock_key, &owner)).await {
+                tracing::warn!("释放{}刷新锁失败: {}", key, err);
+            }
+            return result;
+        }
+        // 其他实例正在刷新，旧凭证未过期时继续使用
+        if let Some(token) = cached {
+            return Ok(token.access_token);
+        }
+        for _ in 0..self.wait_times {
+            tokio::time::sleep(self.wait_interval).await;
+            if let Some(token) = self.store_get(&key).await? {
+                return Ok(token.access_token);
+            }
+        }
+        Err(RPayError::ErrorWithMsg(format!("等待{}刷新超时", key)))
+    }
+
+    /// 同步读取缓存的凭证，参见[`AccessTokenManager::cached`]
+    #[cfg(feature = "blocking")]
+    pub(crate) fn cached_blocking<F>(
+        &self,
+        key: String,
+        refreshing: &tokio::sync::Mutex<()>,
+        fetch: F,
+    ) -> RPayResult<String>
+    where
+        F: FnOnce() -> RPayResult<CachedToken>,
+    {
+        if let Some(token) = self.fresh_token(&key)? {
+            return Ok(token.access_token);
+        }
+        let _guard = refreshing.blocking_lock();
+        let cached = self.store.get(&key)?;
+        if let Some(token) = cached.clone().filter(|token| self.is_fresh(token)) {
+            return Ok(token.access_token);
+        }
+        let lock_key = format!("{}:lock", key);
+        let owner = uuid::Uuid::new_v4().to_string();
+        if self.store.try_lock(&lock_key, &owner, self.lock_ttl)? {
+            let result = match self.fresh_token(&key) {
+                Ok(Some(token)) => Ok(token.access_token),
+                Ok(None) => fetch().and_then(|token| self.save(&key, token)),
+                Err(err) => Err(err),
+            };
+            if let Err(err) = self.store.unlock(&lock_key, &owner) {
+                tracing::warn!("释放{}刷新锁失败: {}", key, err);
+            }
+            return result;
+        }
+        if let Some(token) = cached {
+            return Ok(token.access_token);
+        }
+        for _ in 0..self.wait_times {
+            std::thread::sleep(self.wait_interval);
+            if let Some(token) = self.store.get(&key)? {
+                return Ok(token.access_token);
+            }
+        }
+        Err(RPayError::ErrorWithMsg(format!("等待{}刷新超时", key)))
+    }
+
+    /// 使令牌失效，只有缓存中的令牌与access_token相同时才删除，避免删除其他调用刚刷新的令牌
+    pub fn invalidate(&self, access_token: &str) -> RPayResult<()> {
+        match self.store.get(&self.token_key())? {
+            Some(token) if token.access_token == access_token => self.store.remove(&self.token_key()),
+            _ => Ok(()),
+        }
+    }
+
+    /// 使用访问令牌调用接口，令牌失效(40001/40014/42001)时刷新令牌并重试一次
+    pub async fn call<T, F, Fut>(&self, f: F) -> RPayResult<T>
+    where
+        F: Fn(String) -> Fut,
+        Fut: Future<Output = RPayResult<T>>,
+    {
+        let access_token = self.get_token().await?;
+        match f(access_token.clone()).await {
+            Err(err) if err.is_token_expired() => {
+                let (key, expired) = (self.token_key(), access_token.clone());
+                self.store_op(move |store| match store.get(&key)? {
+                    Some(token) if token.access_token == expired => store.remove(&key),
+                    _ => Ok(()),
+                })
+                .await?;
+                f(self.get_token().await?).await
+            }
+            result => result,
+        }
+    }
+
+    /// 同步调用接口，参见[`AccessTokenManager::call`]
+    #[cfg(feature = "blocking")]
+    pub fn call_blocking<T, F>(&self, f: F) -> RPayResult<T>
+    where
+        F: Fn(String) -> RPayResult<T>,
+    {
+        let access_token = self.get_token_blocking()?;
+        match f(access_token.clone()) {
+            Err(err) if err.is_token_expired() => {
+                self.invalidate(&access_token)?;
+                f(self.get_token_blocking()?)
+            }
+            result => result,
+        }
+    }
+
+    /// 第三方用户唯一凭证
+    pub fn app_id(&self) -> &str {
+        &self.app_id
+    }
+
+    /// 缓存key，格式为`{key_prefix}:{name}:{app_id}`
+    pub(crate) fn cache_key(&self, name: &str) -> String {
+        format!("{}:{}:{}", self.key_prefix, name, self.app_id)
+    }
+
+    // 异步调用时在阻塞线程池中访问存储，避免redis等存储的同步IO阻塞异步运行时的工作线程
+    async fn store_op<T, F>(&self, op: F) -> RPayResult<T>
+    where
+        T: Send + 'static,
+        F: FnOnce(&dyn TokenStore) -> RPayResult<T> + Send + 'static,
+    {
+        let store = self.store.clone();
+        tokio::task::spawn_blocking(move || op(store.as_ref()))
+            .await
+            .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))?
+    }
+
+    async fn store_get(&self, key: &str) -> RPayResult<Option<CachedToken>> {
+        let key = key.to_string();
+        self.store_op(move |store| store.get(&key)).await
+    }
+
+    // 缓存中未到刷新时间的凭证
+    #[cfg(feature = "blocking")]
+    fn fresh_token(&self, key: &str) -> RPayResult<Option<CachedToken>> {
+        Ok(self.store.get(key)?.filter(|token| self.is_fresh(token)))
+    }
+
+    fn is_fresh(&self, token: &CachedToken) -> bool {
+        token.remaining() > self.refresh_ahead
+    }
+
+    fn access_token(&self) -> RPayResult<AccessToken> {
+        Ok(AccessTokenBuilder::default()
+            .app_id(&self.app_id)
+            .secret(&self.secret)
+            .mode(self.mode)
+            .build()?)
+    }
+
+    // 接口返回的令牌
+    fn fetch(&self, resp: Response) -> RPayResult<CachedToken> {
+        let access_token = resp
+            .access_token
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("接口未返回access_token")))?;
+        Ok(CachedToken::new(access_token, resp.expires_in.unwrap_or(7200)))
+    }
+
+    #[cfg(feature = "blocking")]
+    fn save(&self, key: &str, token: CachedToken) -> RPayResult<String> {
+        self.store.set(key, &token)?;
+        Ok(token.access_token)
+    }
+
+    // 普通和稳定版令牌相互独立，使用不同的缓存key
+    fn token_key(&self) -> String {
+        match self.mode {
+            TokenMode::Normal => self.cache_key("access_token"),
+            TokenMode::Stable => self.cache_key("stable_access_token"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    use super::*;
+
+    fn manager(store: Arc<MemoryTokenStore>) -> AccessTokenManager {
+        AccessTokenManagerBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .store(store)
+            .wait_interval(Duration::from_millis(10))
+            .build()
+            .unwrap()
+    }
+
+    /// 测试多个实例共用存储时，并发获取令牌只刷新一次
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_concurrent_refresh() -> RPayResult<()> {
+        let store = Arc::new(MemoryTokenStore::new());
+        let managers = [manager(store.clone()), manager(store.clone())];
+        let fetches = Arc::new(AtomicU32::new(0));
+
+        let tasks = (0..8).map(|i| {
+            let manager = managers[i % 2].clone();
+            let fetches = fetches.clone();
+            tokio::spawn(async move {
+                let key = manager.token_key();
+                manager
+                    .cached(key, &manager.refreshing.clone(), || async move {
+                        fetches.fetch_add(1, Ordering::SeqCst);
+                        tokio::time::sleep(Duration::from_millis(50)).await;
+                        Ok(CachedToken::new("fetched", 7200))
+                    })
+                    .await
+            })
+        });
+        for task in tasks.collect::<Vec<_>>() {
+            assert_eq!(task.await.unwrap()?, "fetched");
+        }
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+        // 刷新完成后锁已释放
+        assert!(store.try_lock("rpay:access_token:wx_app_id:lock", "other", 30)?);
+        Ok(())
+    }
+}
@@ -0,0 +1,187 @@
+use std::fmt::Debug;
+
+use dashmap::{mapref::entry::Entry, DashMap};
+use redis::Commands;
+use serde::{Deserialize, Serialize};
+
+use crate::{utils, RPayResult};
+
+/// 缓存的访问令牌
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CachedToken {
+    /// 访问令牌
+    pub access_token: String,
+    /// 过期时间，unix时间戳，单位：秒
+    pub expires_at: i64,
+}
+
+impl CachedToken {
+    /// 使用有效期创建令牌，expires_in单位：秒
+    pub fn new(access_token: impl Into<String>, expires_in: i64) -> Self {
+        Self {
+            access_token: access_token.into(),
+            expires_at: utils::get_timestamp_secs() + expires_in,
+        }
+    }
+
+    /// 剩余有效时间，单位：秒
+    pub fn remaining(&self) -> i64 {
+        self.expires_at - utils::get_timestamp_secs()
+    }
+
+    /// 是否已过期
+    pub fn is_expired(&self) -> bool {
+        self.remaining() <= 0
+    }
+}
+
+/// 访问令牌存储
+///
+/// 多实例部署时使用共享存储(如redis)，所有实例共用同一个令牌，避免相互刷新导致令牌失效。
+/// 接口为同步接口，[`AccessTokenManager`](super::token_manager::AccessTokenManager)的异步方法会在阻塞线程池中调用。
+pub trait TokenStore: Send + Sync + Debug {
+    /// 读取令牌，已过期的令牌返回None
+    fn get(&self, key: &str) -> RPayResult<Option<CachedToken>>;
+
+    /// 保存令牌，令牌过期后自动删除
+    fn set(&self, key: &str, token: &CachedToken) -> RPayResult<()>;
+
+    /// 删除令牌
+    fn remove(&self, key: &str) -> RPayResult<()>;
+
+    /// 尝试获取刷新锁，value为持有者标识，ttl秒后自动释放，获取成功返回true
+    fn try_lock(&self, key: &str, value: &str, ttl: u64) -> RPayResult<bool>;
+
+    /// 释放刷新锁，只释放value持有的锁
+    fn unlock(&self, key: &str, value: &str) -> RPayResult<()>;
+}
+
+/// 内存令牌存储，适用于单实例部署
+#[derive(Debug, Default)]
+pub struct MemoryTokenStore {
+    tokens: DashMap<String, CachedToken>,
+    // key -> (持有者标识, 过期时间)
+    locks: DashMap<String, (String, i64)>,
+}
+
+impl MemoryTokenStore {
+    /// 创建内存令牌存储
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl TokenStore for MemoryTokenStore {
+    fn get(&self, key: &str) -> RPayResult<Option<CachedToken>> {
+        self.tokens.remove_if(key, |_, token| token.is_expired());
+        Ok(self.tokens.get(key).map(|token| token.clone()))
+    }
+
+    fn set(&self, key: &str, token: &CachedToken) -> RPayResult<()> {
+        self.tokens.insert(key.to_string(), token.clone());
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> RPayResult<()> {
+        self.tokens.remove(key);
+        Ok(())
+    }
+
+    fn try_lock(&self, key: &str, value: &str, ttl: u64) -> RPayResult<bool> {
+        let now = utils::get_timestamp_secs();
+        let lock = (value.to_string(), now + ttl as i64);
+        let locked = match self.locks.entry(key.to_string()) {
+            Entry::Occupied(mut entry) if entry.get().1 <= now => {
+                entry.insert(lock);
+                true
+            }
+            Entry::Occupied(_) => false,
+            Entry::Vacant(entry) => {
+                entry.insert(lock);
+                true
+            }
+        };
+        Ok(locked)
+    }
+
+    fn unlock(&self, key: &str, value: &str) -> RPayResult<()> {
+        self.locks.remove_if(key, |_, (owner, _)| owner == value);
+        Ok(())
+    }
+}
+
+// 只删除自己持有的锁
+const UNLOCK_SCRIPT: &str = r#"
+if redis.call("GET", KEYS[1]) == ARGV[1] then
+    return redis.call("DEL", KEYS[1])
+end
+return 0
+"#;
+
+/// redis令牌存储，多实例共享令牌，刷新锁使用`SET NX EX`实现
+#[derive(Debug, Clone)]
+pub struct RedisTokenStore {
+    pool: r2d2::Pool<redis::Client>,
+}
+
+impl RedisTokenStore {
+    /// 使用已创建的连接池
+    pub fn new(pool: r2d2::Pool<redis::Client>) -> Self {
+        Self { pool }
+    }
+
+    /// 使用redis地址创建连接池，如`redis://127.0.0.1:6379/0`
+    pub fn from_url(url: &str) -> RPayResult<Self> {
+        let client = redis::Client::open(url)?;
+        Ok(Self::new(r2d2::Pool::builder().build(client)?))
+    }
+}
+
+impl TokenStore for RedisTokenStore {
+    fn get(&self, key: &str) -> RPayResult<Option<CachedToken>> {
+        let value: Option<String> = self.pool.get()?.get(key)?;
+        match value {
+            Some(value) => {
+                let token: CachedToken = serde_json::from_str(&value)?;
+                Ok(Some(token).filter(|token| !token.is_expired()))
+            }
+            None => Ok(None),
+        }
+    }
+
+    fn set(&self, key: &str, token: &CachedToken) -> RPayResult<()> {
+        let remaining = token.remaining();
+        if remaining <= 0 {
+            return Ok(());
+        }
+        let value = serde_json::to_string(token)?;
+        self.pool.get()?.set_ex::<_, _, ()>(key, value, remaining as usize)?;
+        Ok(())
+    }
+
+    fn remove(&self, key: &str) -> RPayResult<()> {
+        self.pool.get()?.del::<_, ()>(key)?;
+        Ok(())
+    }
+
+    fn try_lock(&self, key: &str, value: &str, ttl: u64) -> RPayResult<bool> {
+        let mut conn = self.pool.get()?;
+        let result: Option<String> = redis::cmd("SET")
+            .arg(key)
+            .arg(value)
+            .arg("NX")
+            .arg("EX")
+            .arg(ttl)
+            .query(&mut *conn)?;
+        Ok(result.is_some())
+    }
+
+    fn unlock(&self, key: &str, value: &str) -> RPayResult<()> {
+        let mut conn = self.pool.get()?;
+        redis::Script::new(UNLOCK_SCRIPT)
+            .key(key)
+            .arg(value)
+            .invoke::<i32>(&mut *conn)?;
+        Ok(())
+    }
+}
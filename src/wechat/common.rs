@@ -33,3 +33,15 @@ impl Display for HttpMethod {
         }
     }
 }
+
+impl From<&HttpMethod> for reqwest::Method {
+    fn from(method: &HttpMethod) -> Self {
+        match method {
+            HttpMethod::GET => reqwest::Method::GET,
+            HttpMethod::POST => reqwest::Method::POST,
+            HttpMethod::PUT => reqwest::Method::PUT,
+            HttpMethod::DELETE => reqwest::Method::DELETE,
+            HttpMethod::PATCH => reqwest::Method::PATCH,
+        }
+    }
+}
@@ -2,5 +2,7 @@
 pub static ACCEPT: &str = "Accept";
 // 请求头字段
 pub static AUTHORIZATION: &str = "Authorization";
+// 微信支付响应头中的请求id
+pub static REQUEST_ID: &str = "Request-ID";
 // http请求超时时间 30秒
 pub static TIMEOUT: u64 = 30;
\ No newline at end of file
@@ -0,0 +1,389 @@
+use std::path::Path;
+
+use bytes::Bytes;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    Form, Part, RPayError, RPayResult,
+};
+
+/// 上传的多媒体文件
+#[derive(Debug, Clone)]
+pub struct MediaFile {
+    /// 文件名，微信根据扩展名判断文件格式
+    pub file_name: String,
+    /// 文件类型，如image/jpeg
+    pub content_type: Option<String>,
+    /// 文件内容
+    pub content: Bytes,
+}
+
+impl MediaFile {
+    pub fn new<S: Into<String>, B: Into<Bytes>>(file_name: S, content: B) -> Self {
+        MediaFile { file_name: file_name.into(), content_type: None, content: content.into() }
+    }
+
+    /// 读取本地文件
+    pub fn from_path<P: AsRef<Path>>(path: P) -> RPayResult<Self> {
+        let path = path.as_ref();
+        let file_name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .ok_or_else(|| RPayError::ErrorWithMsg(format!("无效的文件路径: {}", path.display())))?;
+        Ok(MediaFile::new(file_name, std::fs::read(path)?))
+    }
+
+    /// 设置文件类型
+    pub fn content_type<S: Into<String>>(mut self, content_type: S) -> Self {
+        self.content_type = Some(content_type.into());
+        self
+    }
+
+    /// 生成multipart表单中的文件
+    pub fn part(&self) -> RPayResult<Part> {
+        let part = Part::bytes(self.content.to_vec()).file_name(self.file_name.clone());
+        Ok(match &self.content_type {
+            Some(content_type) => part.mime_str(content_type)?,
+            None => part,
+        })
+    }
+
+    #[cfg(feature = "blocking")]
+    fn blocking_part(&self) -> RPayResult<reqwest::blocking::multipart::Part> {
+        let part = reqwest::blocking::multipart::Part::bytes(self.content.to_vec()).file_name(self.file_name.clone());
+        Ok(match &self.content_type {
+            Some(content_type) => part.mime_str(content_type)?,
+            None => part,
+        })
+    }
+}
+
+/// 多媒体文件类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MediaType {
+    /// 图片，10M，支持PNG、JPEG、JPG、GIF格式
+    Image,
+    /// 语音，2M，播放长度不超过60s，支持AMR、MP3格式
+    Voice,
+    /// 视频，10MB，支持MP4格式
+    Video,
+    /// 缩略图，64KB，支持JPG格式
+    Thumb,
+}
+
+impl MediaType {
+    fn as_str(&self) -> &'static str {
+        match self {
+            MediaType::Image => "image",
+            MediaType::Voice => "voice",
+            MediaType::Video => "video",
+            MediaType::Thumb => "thumb",
+        }
+    }
+}
+
+/// 临时素材，media_id在上传后3天内有效
+/// https://developers.weixin.qq.com/doc/offiaccount/Asset_Management/New_temporary_materials.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct TempMedia {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl TempMedia {
+    /// 新增临时素材，小程序客服消息的图片也通过该接口上传(仅支持image)
+    pub async fn upload(&self, media_type: MediaType, file: &MediaFile) -> RPayResult<UploadMediaResponse> {
+        self.upload_request(media_type)?
+            .send_multipart(Form::new().part("media", file.part()?))
+            .await
+    }
+
+    /// 新增临时素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn upload_blocking(&self, media_type: MediaType, file: &MediaFile) -> RPayResult<UploadMediaResponse> {
+        self.upload_request(media_type)?
+            .send_multipart_blocking(reqwest::blocking::multipart::Form::new().part("media", file.blocking_part()?))
+    }
+
+    /// 获取临时素材，视频素材返回包含video_url的JSON内容
+    pub async fn get(&self, media_id: &str) -> RPayResult<Bytes> {
+        Ok(self.get_request(media_id)?.send_bytes().await?.into_body())
+    }
+
+    /// 获取临时素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_blocking(&self, media_id: &str) -> RPayResult<Bytes> {
+        Ok(self.get_request(media_id)?.send_bytes_blocking()?.into_body())
+    }
+
+    fn upload_request(&self, media_type: MediaType) -> RPayResult<Request> {
+        let url = format!(
+            "https://api.weixin.qq.com/cgi-bin/media/upload?access_token={}&type={}",
+            self.access_token,
+            media_type.as_str()
+        );
+        Ok(RequestBuilder::default().url(url).method(HttpMethod::POST).build()?)
+    }
+
+    fn get_request(&self, media_id: &str) -> RPayResult<Request> {
+        let url = reqwest::Url::parse_with_params(
+            "https://api.weixin.qq.com/cgi-bin/media/get",
+            &[("access_token", self.access_token.as_str()), ("media_id", media_id)],
+        )
+        .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))?;
+        Ok(RequestBuilder::default().url(url.to_string()).build()?)
+    }
+}
+
+/// 永久素材
+/// https://developers.weixin.qq.com/doc/offiaccount/Asset_Management/Adding_Permanent_Assets.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct Material {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+/// 视频素材的描述信息，上传视频素材时必填
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoDescription {
+    /// 视频素材的标题
+    pub title: String,
+    /// 视频素材的描述
+    pub introduction: String,
+}
+
+/// 素材的类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MaterialType {
+    /// 图片
+    Image,
+    /// 视频
+    Video,
+    /// 语音
+    Voice,
+    /// 图文
+    News,
+}
+
+impl Material {
+    /// 新增永久素材，上传视频素材时需要提供描述信息
+    pub async fn add(
+        &self,
+        media_type: MediaType,
+        file: &MediaFile,
+        description: Option<&VideoDescription>,
+    ) -> RPayResult<AddMaterialResponse> {
+        let mut form = Form::new().part("media", file.part()?);
+        if let Some(description) = Self::description(media_type, description)? {
+            form = form.text("description", description);
+        }
+        self.add_request(media_type)?.send_multipart(form).await
+    }
+
+    /// 新增永久素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn add_blocking(
+        &self,
+        media_type: MediaType,
+        file: &MediaFile,
+        description: Option<&VideoDescription>,
+    ) -> RPayResult<AddMaterialResponse> {
+        let mut form = reqwest::blocking::multipart::Form::new().part("media", file.blocking_part()?);
+        if let Some(description) = Self::description(media_type, description)? {
+            form = form.text("description", description);
+        }
+        self.add_request(media_type)?.send_multipart_blocking(form)
+    }
+
+    /// 获取永久素材，图片、语音和缩略图返回文件内容，视频和图文返回JSON内容
+    pub async fn get(&self, media_id: &str) -> RPayResult<Bytes> {
+        Ok(self.media_request("/cgi-bin/material/get_material", media_id)?.send_bytes().await?.into_body())
+    }
+
+    /// 获取永久素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_blocking(&self, media_id: &str) -> RPayResult<Bytes> {
+        Ok(self.media_request("/cgi-bin/material/get_material", media_id)?.send_bytes_blocking()?.into_body())
+    }
+
+    /// 获取视频永久素材的标题、描述和下载地址
+    pub async fn get_video(&self, media_id: &str) -> RPayResult<VideoMaterial> {
+        self.media_request("/cgi-bin/material/get_material", media_id)?.send().await
+    }
+
+    /// 获取视频永久素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_video_blocking(&self, media_id: &str) -> RPayResult<VideoMaterial> {
+        self.media_request("/cgi-bin/material/get_material", media_id)?.send_blocking()
+    }
+
+    /// 删除永久素材
+    pub async fn delete(&self, media_id: &str) -> RPayResult<MaterialResponse> {
+        self.media_request("/cgi-bin/material/del_material", media_id)?.send().await
+    }
+
+    /// 删除永久素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn delete_blocking(&self, media_id: &str) -> RPayResult<MaterialResponse> {
+        self.media_request("/cgi-bin/material/del_material", media_id)?.send_blocking()
+    }
+
+    /// 获取素材总数
+    pub async fn count(&self) -> RPayResult<MaterialCountResponse> {
+        self.count_request()?.send().await
+    }
+
+    /// 获取素材总数(同步)
+    #[cfg(feature = "blocking")]
+    pub fn count_blocking(&self) -> RPayResult<MaterialCountResponse> {
+        self.count_request()?.send_blocking()
+    }
+
+    /// 获取素材列表，offset从0开始，count取值在1到20之间
+    pub async fn batch_get(&self, material_type: MaterialType, offset: u32, count: u32) -> RPayResult<BatchGetMaterialResponse> {
+        self.batch_get_request(material_type, offset, count)?.send().await
+    }
+
+    /// 获取素材列表(同步)
+    #[cfg(feature = "blocking")]
+    pub fn batch_get_blocking(&self, material_type: MaterialType, offset: u32, count: u32) -> RPayResult<BatchGetMaterialResponse> {
+        self.batch_get_request(material_type, offset, count)?.send_blocking()
+    }
+
+    // 视频素材必须提供描述信息，序列化为JSON文本字段
+    fn description(media_type: MediaType, description: Option<&VideoDescription>) -> RPayResult<Option<String>> {
+        match (media_type, description) {
+            (MediaType::Video, None) => Err(RPayError::ErrorWithMsg(String::from("上传视频素材时必须提供描述信息"))),
+            (_, Some(description)) => Ok(Some(serde_json::to_string(description)?)),
+            (_, None) => Ok(None),
+        }
+    }
+
+    fn add_request(&self, media_type: MediaType) -> RPayResult<Request> {
+        let url = format!(
+            "https://api.weixin.qq.com/cgi-bin/material/add_material?access_token={}&type={}",
+            self.access_token,
+            media_type.as_str()
+        );
+        Ok(RequestBuilder::default().url(url).method(HttpMethod::POST).build()?)
+    }
+
+    fn media_request(&self, path: &str, media_id: &str) -> RPayResult<Request> {
+        self.post_request(path, json!({ "media_id": media_id }))
+    }
+
+    fn count_request(&self) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com/cgi-bin/material/get_materialcount?access_token={}", self.access_token);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+
+    fn batch_get_request(&self, material_type: MaterialType, offset: u32, count: u32) -> RPayResult<Request> {
+        self.post_request(
+            "/cgi-bin/material/batchget_material",
+            json!({ "type": material_type, "offset": offset, "count": count }),
+        )
+    }
+
+    fn post_request(&self, path: &str, body: Value) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(body.to_string())
+            .build()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UploadMediaResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 媒体文件类型
+    #[serde(rename = "type")]
+    pub media_type: Option<MediaType>,
+    /// 媒体文件上传后获取的唯一标识，缩略图返回thumb_media_id
+    #[serde(alias = "thumb_media_id")]
+    pub media_id: Option<String>,
+    /// 媒体文件上传时间戳
+    pub created_at: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddMaterialResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 新增的永久素材的media_id
+    pub media_id: Option<String>,
+    /// 新增的图片素材的图片URL(仅新增图片素材时会返回该字段)
+    pub url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoMaterial {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 视频素材的标题
+    pub title: Option<String>,
+    /// 视频素材的描述
+    pub description: Option<String>,
+    /// 视频素材的下载地址
+    pub down_url: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialCountResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 语音总数量
+    pub voice_count: Option<i64>,
+    /// 视频总数量
+    pub video_count: Option<i64>,
+    /// 图片总数量
+    pub image_count: Option<i64>,
+    /// 图文总数量
+    pub news_count: Option<i64>,
+}
+
+/// 素材列表项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MaterialItem {
+    /// 素材的media_id
+    pub media_id: String,
+    /// 文件名称，图文素材没有该字段
+    pub name: Option<String>,
+    /// 最后更新时间
+    pub update_time: Option<i64>,
+    /// 图片的URL，仅图片素材返回
+    pub url: Option<String>,
+    /// 图文素材的内容
+    pub content: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchGetMaterialResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 该类型的素材的总数
+    pub total_count: Option<i64>,
+    /// 本次调用获取的素材的数量
+    pub item_count: Option<i64>,
+    /// 素材列表
+    #[serde(default)]
+    pub item: Vec<MaterialItem>,
+}
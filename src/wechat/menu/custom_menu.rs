@@ -0,0 +1,266 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayResult,
+};
+
+/// 自定义菜单，最多包括3个一级菜单，每个一级菜单最多包含5个二级菜单
+/// https://developers.weixin.qq.com/doc/offiaccount/Custom_Menus/Creating_Custom-Defined_Menu.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct CustomMenu {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl CustomMenu {
+    /// 创建默认菜单，会覆盖已有的默认菜单
+    pub async fn create(&self, menu: &Menu) -> RPayResult<MenuResponse> {
+        self.post_request("/cgi-bin/menu/create", serde_json::to_value(menu)?)?.send().await
+    }
+
+    /// 创建默认菜单(同步)
+    #[cfg(feature = "blocking")]
+    pub fn create_blocking(&self, menu: &Menu) -> RPayResult<MenuResponse> {
+        self.post_request("/cgi-bin/menu/create", serde_json::to_value(menu)?)?.send_blocking()
+    }
+
+    /// 查询通过接口创建的默认菜单和个性化菜单
+    pub async fn get(&self) -> RPayResult<GetMenuResponse> {
+        self.get_request("/cgi-bin/menu/get")?.send().await
+    }
+
+    /// 查询菜单(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_blocking(&self) -> RPayResult<GetMenuResponse> {
+        self.get_request("/cgi-bin/menu/get")?.send_blocking()
+    }
+
+    /// 删除默认菜单，同时删除全部个性化菜单
+    pub async fn delete(&self) -> RPayResult<MenuResponse> {
+        self.get_request("/cgi-bin/menu/delete")?.send().await
+    }
+
+    /// 删除默认菜单(同步)
+    #[cfg(feature = "blocking")]
+    pub fn delete_blocking(&self) -> RPayResult<MenuResponse> {
+        self.get_request("/cgi-bin/menu/delete")?.send_blocking()
+    }
+
+    /// 创建个性化菜单，菜单必须设置matchrule，需要先创建默认菜单
+    pub async fn add_conditional(&self, menu: &Menu) -> RPayResult<AddConditionalResponse> {
+        self.post_request("/cgi-bin/menu/addconditional", serde_json::to_value(menu)?)?.send().await
+    }
+
+    /// 创建个性化菜单(同步)
+    #[cfg(feature = "blocking")]
+    pub fn add_conditional_blocking(&self, menu: &Menu) -> RPayResult<AddConditionalResponse> {
+        self.post_request("/cgi-bin/menu/addconditional", serde_json::to_value(menu)?)?.send_blocking()
+    }
+
+    /// 删除个性化菜单
+    pub async fn delete_conditional(&self, menuid: &str) -> RPayResult<MenuResponse> {
+        self.post_request("/cgi-bin/menu/delconditional", json!({ "menuid": menuid }))?.send().await
+    }
+
+    /// 删除个性化菜单(同步)
+    #[cfg(feature = "blocking")]
+    pub fn delete_conditional_blocking(&self, menuid: &str) -> RPayResult<MenuResponse> {
+        self.post_request("/cgi-bin/menu/delconditional", json!({ "menuid": menuid }))?.send_blocking()
+    }
+
+    /// 测试个性化菜单匹配结果，user_id可以是粉丝的OpenID，也可以是粉丝的微信号
+    pub async fn try_match(&self, user_id: &str) -> RPayResult<TryMatchResponse> {
+        self.post_request("/cgi-bin/menu/trymatch", json!({ "user_id": user_id }))?.send().await
+    }
+
+    /// 测试个性化菜单匹配结果(同步)
+    #[cfg(feature = "blocking")]
+    pub fn try_match_blocking(&self, user_id: &str) -> RPayResult<TryMatchResponse> {
+        self.post_request("/cgi-bin/menu/trymatch", json!({ "user_id": user_id }))?.send_blocking()
+    }
+
+    fn get_request(&self, path: &str) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+
+    fn post_request(&self, path: &str, body: Value) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(body.to_string())
+            .build()?)
+    }
+}
+
+/// 菜单
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Menu {
+    /// 一级菜单数组，个数应为1~3个
+    pub button: Vec<Button>,
+    /// 个性化菜单的匹配规则，默认菜单不设置
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matchrule: Option<MatchRule>,
+    /// 菜单id，查询菜单时返回
+    #[serde(skip_serializing)]
+    pub menuid: Option<Value>,
+}
+
+impl Menu {
+    /// 默认菜单
+    pub fn new(button: Vec<Button>) -> Self {
+        Menu { button, ..Default::default() }
+    }
+
+    /// 个性化菜单
+    pub fn conditional(button: Vec<Button>, matchrule: MatchRule) -> Self {
+        Menu { button, matchrule: Some(matchrule), ..Default::default() }
+    }
+}
+
+/// 菜单按钮，包含子菜单的一级菜单没有响应动作
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Button {
+    /// 菜单标题，不超过16个字节，子菜单不超过60个字节
+    pub name: String,
+    /// 菜单的响应动作
+    #[serde(flatten)]
+    pub action: Option<ButtonAction>,
+    /// 二级菜单数组，个数应为1~5个
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub sub_button: Vec<Button>,
+}
+
+impl Button {
+    pub fn new<S: Into<String>>(name: S, action: ButtonAction) -> Self {
+        Button { name: name.into(), action: Some(action), sub_button: Vec::new() }
+    }
+
+    /// 包含二级菜单的一级菜单
+    pub fn sub<S: Into<String>>(name: S, sub_button: Vec<Button>) -> Self {
+        Button { name: name.into(), action: None, sub_button }
+    }
+
+    /// 点击推事件
+    pub fn click<S: Into<String>, K: Into<String>>(name: S, key: K) -> Self {
+        Button::new(name, ButtonAction::Click { key: key.into() })
+    }
+
+    /// 跳转URL
+    pub fn view<S: Into<String>, U: Into<String>>(name: S, url: U) -> Self {
+        Button::new(name, ButtonAction::View { url: url.into() })
+    }
+
+    /// 跳转小程序，url为不支持小程序的老版本客户端打开的网页
+    pub fn miniprogram<S: Into<String>>(name: S, appid: &str, pagepath: &str, url: &str) -> Self {
+        Button::new(
+            name,
+            ButtonAction::Miniprogram { url: url.to_string(), appid: appid.to_string(), pagepath: pagepath.to_string() },
+        )
+    }
+}
+
+/// 菜单的响应动作类型
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ButtonAction {
+    /// 点击推事件，推送CLICK事件
+    Click { key: String },
+    /// 跳转URL，推送VIEW事件
+    View { url: String },
+    /// 跳转小程序，推送view_miniprogram事件
+    Miniprogram { url: String, appid: String, pagepath: String },
+    /// 扫码推事件，推送scancode_push事件
+    ScancodePush { key: String },
+    /// 扫码推事件且弹出"消息接收中"提示框，推送scancode_waitmsg事件
+    ScancodeWaitmsg { key: String },
+    /// 弹出系统拍照发图，推送pic_sysphoto事件
+    PicSysphoto { key: String },
+    /// 弹出拍照或者相册发图，推送pic_photo_or_album事件
+    PicPhotoOrAlbum { key: String },
+    /// 弹出微信相册发图器，推送pic_weixin事件
+    PicWeixin { key: String },
+    /// 弹出地理位置选择器，推送location_select事件
+    LocationSelect { key: String },
+    /// 下发消息(除文本消息)，media_id为永久素材id
+    MediaId { media_id: String },
+    /// 跳转图文消息，article_id为发布后获得的id
+    ArticleId { article_id: String },
+    /// 跳转图文消息，不可被分享
+    ArticleViewLimited { article_id: String },
+}
+
+/// 个性化菜单的匹配规则，至少设置一个字段
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable", default)]
+pub struct MatchRule {
+    /// 用户标签的id，可通过用户标签管理接口获取
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tag_id: Option<String>,
+    /// 性别：男(1)女(2)，不填则不做匹配
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub sex: Option<String>,
+    /// 国家信息
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub country: Option<String>,
+    /// 省份信息
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub province: Option<String>,
+    /// 城市信息
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub city: Option<String>,
+    /// 客户端版本：IOS(1)，Android(2)，Others(3)
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_platform_type: Option<String>,
+    /// 语言信息，如zh_CN
+    #[builder(setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MenuResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GetMenuResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 默认菜单
+    pub menu: Option<Menu>,
+    /// 个性化菜单列表
+    #[serde(default)]
+    pub conditionalmenu: Vec<Menu>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddConditionalResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 个性化菜单id
+    pub menuid: Option<Value>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TryMatchResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 匹配到的菜单
+    #[serde(default)]
+    pub button: Vec<Button>,
+}
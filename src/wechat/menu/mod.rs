@@ -0,0 +1,2 @@
+pub mod custom_menu;
+pub use custom_menu::*;
@@ -1,68 +1,140 @@
 use std::fmt::{Display, Formatter};
 
 use derive_builder::Builder;
-use reqwest::Client;
 use serde::{ Deserialize, Serialize};
+use serde_json::json;
 
 /*
 
 miniprogrampage	object	否	小程序卡片，msgtype="miniprogrampage" 时必填
 */
-use crate::RPayResult;
+use crate::{common::HttpMethod, core::request::{Request, RequestBuilder}, media::{AddMaterialResponse, Material, MediaFile, MediaType}, RPayResult};
 
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 #[builder(pattern = "mutable")]
 pub struct CustomerService {
     /// 接口调用凭证，该参数为 URL 参数，非 Body 参数。使用getAccessToken 或者 authorizer_access_token
     #[builder(setter(into))]
+    #[serde(skip)]
     pub access_token: String,
     // 用户的 OpenID
     #[builder(setter(into))]
     pub touser: String,
-    /// 消息类型。text表示文本消息；image表示图片消息；link表示图文链接；miniprogrampage表示小程序卡片。
+    /// 消息类型
     #[builder(setter(into))]
     #[serde(rename = "msgtype")]
     pub msg_type: MsgType,
     /// 文本消息，msgtype="text" 时必填
     #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub text: Option<Text>,
     ///  图片消息，msgtype="image" 时必填
     #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub image : Option<Image>,
+    /// 语音消息，msgtype="voice" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub voice: Option<Voice>,
+    /// 视频消息，msgtype="video" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<Video>,
+    /// 音乐消息，msgtype="music" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub music: Option<Music>,
+    /// 图文消息(点击跳转到外链)，msgtype="news" 时必填，图文消息条数限制在1条以内
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub news: Option<News>,
+    /// 图文消息(点击跳转到图文消息页面)，msgtype="mpnews" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpnews: Option<Mpnews>,
+    /// 已发布的图文消息，msgtype="mpnewsarticle" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mpnewsarticle: Option<MpnewsArticle>,
+    /// 菜单消息，msgtype="msgmenu" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub msgmenu: Option<MsgMenu>,
+    /// 卡券，msgtype="wxcard" 时必填
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wxcard: Option<WxCard>,
     /// 图文链接，msgtype="link" 时必填
     #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub link: Option<Link>,
     /// 小程序卡片，msgtype="miniprogrampage" 时必填
     #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub miniprogrampage: Option<Miniprogrampage>,
+    /// 以某个客服帐号来发消息
+    #[builder(default, setter(into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub customservice: Option<CustomServiceAccount>,
 }
 
 impl CustomerService {
 
     pub async fn send(&mut self) -> RPayResult<Response> {
+        let resp = self.send_request()?.send::<Response>().await?;
+        Ok(resp)
+    }
+
+    /// 发送客服消息(同步)
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(&mut self) -> RPayResult<Response> {
+        self.send_request()?.send_blocking::<Response>()
+    }
+
+    /// 下发客服输入状态，command为Typing时对用户下发"正在输入"状态，CancelTyping取消
+    pub async fn typing(&self, command: TypingCommand) -> RPayResult<Response> {
+        self.typing_request(command)?.send().await
+    }
+
+    /// 下发客服输入状态(同步)
+    #[cfg(feature = "blocking")]
+    pub fn typing_blocking(&self, command: TypingCommand) -> RPayResult<Response> {
+        self.typing_request(command)?.send_blocking()
+    }
+
+    /// 上传缩略图永久素材，返回的media_id用于视频、音乐等消息的thumb_media_id
+    pub async fn get_thumb_media_id(&self, file: &MediaFile) -> RPayResult<AddMaterialResponse> {
+        self.material().add(MediaType::Thumb, file, None).await
+    }
+
+    /// 上传缩略图永久素材(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_thumb_media_id_blocking(&self, file: &MediaFile) -> RPayResult<AddMaterialResponse> {
+        self.material().add_blocking(MediaType::Thumb, file, None)
+    }
+
+    fn material(&self) -> Material {
+        Material { access_token: self.access_token.clone() }
+    }
+
+    fn send_request(&self) -> RPayResult<Request> {
         let url = format!("https://api.weixin.qq.com/cgi-bin/message/custom/send?access_token={}", self.access_token);
-        let json_body = serde_json::to_string(self).unwrap();
-        println!("{}", json_body);
-        let resp = Client::new()
-            .post(url)
+        let json_body = serde_json::to_string(self)?;
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
             .body(json_body)
-            .send()
-            .await?
-            .json::<Response>()
-            .await?;
-        Ok(resp)
+            .build()?)
     }
 
-    pub async fn get_thumb_media_id(&mut self, _text: String) -> RPayResult<Response> {
-        let url = format!("https://api.weixin.qq.com/cgi-bin/material/add_material?access_token={}&type=thumb", self.access_token);
-        let resp = Client::new()
-            .post(url)
-            .send()
-            .await?
-            .json::<Response>()
-            .await?;
-        println!("{:?}", resp);
-        Ok(resp)
+    fn typing_request(&self, command: TypingCommand) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com/cgi-bin/message/custom/typing?access_token={}", self.access_token);
+        let json_body = serde_json::to_string(&json!({ "touser": self.touser, "command": command }))?;
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(json_body)
+            .build()?)
     }
 }
 
@@ -108,10 +180,143 @@ pub struct Text {
     pub content: String,
 }
 
+/// 语音消息，msgtype="voice" 时必填
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Voice {
+    /// 发送的语音的媒体ID
+    pub media_id: String,
+}
+
+/// 视频消息，msgtype="video" 时必填
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Video {
+    /// 发送的视频的媒体ID
+    pub media_id: String,
+    /// 缩略图/视频封面的媒体ID
+    pub thumb_media_id: String,
+    /// 视频消息的标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 视频消息的描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// 音乐消息，msgtype="music" 时必填
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Music {
+    /// 音乐标题
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 音乐描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 音乐链接
+    pub musicurl: String,
+    /// 高品质音乐链接，wifi环境优先使用该链接播放音乐
+    pub hqmusicurl: String,
+    /// 缩略图的媒体ID
+    pub thumb_media_id: String,
+}
+
+/// 图文消息(点击跳转到外链)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct News {
+    pub articles: Vec<NewsArticle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewsArticle {
+    /// 图文消息标题
+    pub title: String,
+    /// 图文消息描述
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// 图文消息被点击后跳转的链接
+    pub url: String,
+    /// 图文消息的图片链接，支持JPG、PNG格式，较好的效果为大图640*320，小图80*80
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub picurl: Option<String>,
+}
+
+/// 图文消息(点击跳转到图文消息页面)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Mpnews {
+    /// 图文消息的媒体ID
+    pub media_id: String,
+}
+
+/// 已发布的图文消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MpnewsArticle {
+    /// 发布后获得的article_id
+    pub article_id: String,
+}
+
+/// 菜单消息，用户点击菜单后会收到一条文本消息，内容为菜单内容，并带有bizmsgmenuid
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MsgMenu {
+    /// 菜单上方的内容
+    pub head_content: String,
+    /// 菜单选项
+    pub list: Vec<MsgMenuItem>,
+    /// 菜单下方的内容
+    pub tail_content: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MsgMenuItem {
+    /// 菜单id
+    pub id: String,
+    /// 菜单内容
+    pub content: String,
+}
+
+/// 卡券，仅支持非自定义Code码和导入code模式的卡券
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WxCard {
+    /// 卡券id
+    pub card_id: String,
+}
+
+/// 发送消息的客服帐号
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CustomServiceAccount {
+    /// 完整客服帐号，格式为：帐号前缀@公众号微信号
+    pub kf_account: String,
+}
+
+/// 客服输入状态
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TypingCommand {
+    /// 正在输入
+    Typing,
+    /// 取消正在输入
+    CancelTyping,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum  MsgType {
     #[serde(rename = "text")]
     Text,
+    #[serde(rename = "image")]
+    Image,
+    #[serde(rename = "voice")]
+    Voice,
+    #[serde(rename = "video")]
+    Video,
+    #[serde(rename = "music")]
+    Music,
+    #[serde(rename = "news")]
+    News,
+    #[serde(rename = "mpnews")]
+    Mpnews,
+    #[serde(rename = "mpnewsarticle")]
+    Mpnewsarticle,
+    #[serde(rename = "msgmenu")]
+    Msgmenu,
+    #[serde(rename = "wxcard")]
+    Wxcard,
     #[serde(rename = "link")]
     Link,
     #[serde(rename = "miniprogrampage")]
@@ -122,8 +327,17 @@ impl Display for MsgType {
     fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
             MsgType::Text => write!(fmt, "text"),
+            MsgType::Image => write!(fmt, "image"),
+            MsgType::Voice => write!(fmt, "voice"),
+            MsgType::Video => write!(fmt, "video"),
+            MsgType::Music => write!(fmt, "music"),
+            MsgType::News => write!(fmt, "news"),
+            MsgType::Mpnews => write!(fmt, "mpnews"),
+            MsgType::Mpnewsarticle => write!(fmt, "mpnewsarticle"),
+            MsgType::Msgmenu => write!(fmt, "msgmenu"),
+            MsgType::Wxcard => write!(fmt, "wxcard"),
             MsgType::Link => write!(fmt, "link"),
             MsgType::Miniprogrampage => write!(fmt, "miniprogrampage"),
         }
     }
-}
\ No newline at end of file
+}
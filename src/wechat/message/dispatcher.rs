@@ -0,0 +1,196 @@
+use std::{
+    future::Future,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+use derive_builder::Builder;
+use futures_util::{stream, StreamExt};
+use tokio::{sync::Mutex, time::Instant};
+
+use crate::{auth::token_manager::AccessTokenManager, RPayError, RPayResult};
+
+use super::message::{Message, Response};
+
+// 用户拒绝接受消息或订阅次数已用完
+const USER_REFUSED: &str = "43101";
+// 接口调用超过限额
+const API_QUOTA_LIMIT: &str = "45009";
+
+/// 订阅消息批量发送器
+///
+/// - 最多同时发送`concurrency`条消息，每秒最多发送`rate_limit`条
+/// - 43101为用户拒收或订阅次数不足，不再重试
+/// - 45009为接口调用超过限额，所有发送暂停`backoff`后重试，每次重试暂停时间翻倍
+/// - 40001/40014/42001通过[`AccessTokenManager::call`]刷新令牌后重试
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct SubscribeDispatcher {
+    /// 访问令牌管理器
+    token_manager: AccessTokenManager,
+    /// 最大并发数
+    #[builder(default = "10")]
+    concurrency: usize,
+    /// 每秒最多发送条数
+    #[builder(default = "20")]
+    rate_limit: u32,
+    /// 接口调用超过限额时的暂停时间
+    #[builder(default = "Duration::from_secs(1)")]
+    backoff: Duration,
+    /// 接口调用超过限额时的最大重试次数
+    #[builder(default = "3")]
+    max_retries: u32,
+    #[builder(setter(skip), default = "Arc::new(Mutex::new(Instant::now()))")]
+    next_slot: Arc<Mutex<Instant>>,
+}
+
+/// 单个接收者的发送结果
+#[derive(Debug)]
+pub enum DispatchStatus {
+    /// 发送成功
+    Sent(Response),
+    /// 用户拒收或订阅次数不足(43101)
+    Refused(RPayError),
+    /// 发送失败，包括超过重试次数的限额异常
+    Failed(RPayError),
+}
+
+/// 单个接收者的发送结果
+#[derive(Debug)]
+pub struct DispatchOutcome {
+    /// 接收者openid
+    pub touser: String,
+    /// 发送结果
+    pub status: DispatchStatus,
+    /// 发送次数，包括令牌刷新和限额重试
+    pub attempts: u32,
+}
+
+impl DispatchOutcome {
+    /// 是否发送成功
+    pub fn is_sent(&self) -> bool {
+        matches!(self.status, DispatchStatus::Sent(_))
+    }
+}
+
+/// 批量发送报告，结果顺序与发送的消息顺序一致
+#[derive(Debug, Default)]
+pub struct DispatchReport {
+    pub outcomes: Vec<DispatchOutcome>,
+}
+
+impl DispatchReport {
+    /// 发送成功的数量
+    pub fn sent(&self) -> usize {
+        self.outcomes.iter().filter(|outcome| outcome.is_sent()).count()
+    }
+
+    /// 用户拒收的接收者
+    pub fn refused(&self) -> Vec<&str> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome.status, DispatchStatus::Refused(_)))
+            .map(|outcome| outcome.touser.as_str())
+            .collect()
+    }
+
+    /// 发送失败的结果
+    pub fn failed(&self) -> Vec<&DispatchOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|outcome| matches!(outcome.status, DispatchStatus::Failed(_)))
+            .collect()
+    }
+}
+
+impl SubscribeDispatcher {
+    /// 使用默认配置创建发送器
+    pub fn new(token_manager: AccessTokenManager) -> RPayResult<Self> {
+        Ok(SubscribeDispatcherBuilder::default().token_manager(token_manager).build()?)
+    }
+
+    /// 批量发送订阅消息，消息中的access_token由令牌管理器填充，构建消息时可传空字符串
+    pub async fn dispatch(&self, messages: Vec<Message>) -> DispatchReport {
+        self.dispatch_with(messages, |mut message| async move { message.send().await })
+            .await
+    }
+
+    /// 使用自定义发送方法批量发送，发送方法接收已填充access_token的消息
+    pub async fn dispatch_with<F, Fut>(&self, messages: Vec<Message>, send: F) -> DispatchReport
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = RPayResult<Response>>,
+    {
+        let outcomes = stream::iter(messages)
+            .map(|message| self.send_one(message, &send))
+            .buffered(self.concurrency.max(1))
+            .collect()
+            .await;
+        DispatchReport { outcomes }
+    }
+
+    async fn send_one<F, Fut>(&self, message: Message, send: &F) -> DispatchOutcome
+    where
+        F: Fn(Message) -> Fut,
+        Fut: Future<Output = RPayResult<Response>>,
+    {
+        let attempts = AtomicU32::new(0);
+        let mut backoff = self.backoff;
+        let mut retries = 0;
+        let status = loop {
+            let result = self
+                .token_manager
+                .call(|access_token| {
+                    let mut message = message.clone();
+                    message.access_token = access_token;
+                    attempts.fetch_add(1, Ordering::Relaxed);
+                    async {
+                        self.acquire().await;
+                        send(message).await
+                    }
+                })
+                .await;
+            match result {
+                Ok(resp) => break DispatchStatus::Sent(resp),
+                Err(err) if err.api_code() == Some(USER_REFUSED) => break DispatchStatus::Refused(err),
+                Err(err) if err.api_code() == Some(API_QUOTA_LIMIT) && retries < self.max_retries => {
+                    retries += 1;
+                    tracing::warn!("订阅消息发送超过接口限额，暂停{:?}后重试", backoff);
+                    self.pause(backoff).await;
+                    backoff *= 2;
+                }
+                Err(err) => break DispatchStatus::Failed(err),
+            }
+        };
+        DispatchOutcome {
+            touser: message.touser,
+            status,
+            attempts: attempts.into_inner(),
+        }
+    }
+
+    // 按发送速率分配发送时间，等待到分配的时间再发送
+    async fn acquire(&self) {
+        let interval = Duration::from_secs(1) / self.rate_limit.max(1);
+        let slot = {
+            let mut next_slot = self.next_slot.lock().await;
+            let slot = (*next_slot).max(Instant::now());
+            *next_slot = slot + interval;
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+
+    // 暂停所有发送
+    async fn pause(&self, duration: Duration) {
+        let resume = Instant::now() + duration;
+        {
+            let mut next_slot = self.next_slot.lock().await;
+            *next_slot = (*next_slot).max(resume);
+        }
+        tokio::time::sleep_until(resume).await;
+    }
+}
@@ -0,0 +1,687 @@
+use quick_xml::{events::Event, Reader};
+use serde::{de::DeserializeOwned, Deserialize, Deserializer, Serialize};
+use serde_json::{Map, Value};
+
+use crate::{security::MediaCheckEvent, RPayError, RPayResult};
+
+/// 消息推送的数据格式
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum PushFormat {
+    #[default]
+    Xml,
+    Json,
+}
+
+impl PushFormat {
+    /// 根据消息内容判断数据格式
+    pub fn detect(body: &str) -> Self {
+        match body.trim_start().starts_with('{') {
+            true => PushFormat::Json,
+            false => PushFormat::Xml,
+        }
+    }
+
+    /// 将消息解析为字段表，XML中的子元素解析为对象，重复的元素解析为数组
+    pub fn parse(&self, body: &str) -> RPayResult<Map<String, Value>> {
+        match self {
+            PushFormat::Xml => xml_to_map(body),
+            PushFormat::Json => Ok(serde_json::from_str(body)?),
+        }
+    }
+}
+
+/// 消息公共字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MessageHeader {
+    /// 接收方帐号(公众号或小程序的原始ID)
+    #[serde(rename = "ToUserName")]
+    pub to_user_name: String,
+    /// 发送方帐号(一个OpenID)
+    #[serde(rename = "FromUserName")]
+    pub from_user_name: String,
+    /// 消息创建时间(整型)
+    #[serde(rename = "CreateTime", deserialize_with = "int")]
+    pub create_time: i64,
+    /// 消息类型
+    #[serde(rename = "MsgType")]
+    pub msg_type: String,
+}
+
+/// 文本消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TextMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 文本消息内容
+    #[serde(rename = "Content")]
+    pub content: String,
+    /// 消息id，64位整型
+    #[serde(rename = "MsgId", deserialize_with = "int")]
+    pub msg_id: i64,
+}
+
+/// 图片消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImageMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 图片链接(由系统生成)
+    #[serde(rename = "PicUrl")]
+    pub pic_url: String,
+    /// 图片消息媒体id，可以调用获取临时素材接口拉取数据
+    #[serde(rename = "MediaId")]
+    pub media_id: String,
+    /// 消息id，64位整型
+    #[serde(rename = "MsgId", deserialize_with = "int")]
+    pub msg_id: i64,
+}
+
+/// 语音消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoiceMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 语音消息媒体id
+    #[serde(rename = "MediaId")]
+    pub media_id: String,
+    /// 语音格式，如amr，speex等
+    #[serde(rename = "Format")]
+    pub format: String,
+    /// 语音识别结果，开通语音识别后才有
+    #[serde(rename = "Recognition", default)]
+    pub recognition: Option<String>,
+    /// 消息id，64位整型
+    #[serde(rename = "MsgId", deserialize_with = "int")]
+    pub msg_id: i64,
+}
+
+/// 视频消息和小视频消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VideoMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 视频消息媒体id
+    #[serde(rename = "MediaId")]
+    pub media_id: String,
+    /// 视频消息缩略图的媒体id
+    #[serde(rename = "ThumbMediaId")]
+    pub thumb_media_id: String,
+    /// 消息id，64位整型
+    #[serde(rename = "MsgId", deserialize_with = "int")]
+    pub msg_id: i64,
+}
+
+/// 地理位置消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 地理位置纬度
+    #[serde(rename = "Location_X", deserialize_with = "float")]
+    pub location_x: f64,
+    /// 地理位置经度
+    #[serde(rename = "Location_Y", deserialize_with = "float")]
+    pub location_y: f64,
+    /// 地图缩放大小
+    #[serde(rename = "Scale", deserialize_with = "int")]
+    pub scale: i64,
+    /// 地理位置信息
+    #[serde(rename = "Label")]
+    pub label: String,
+    /// 消息id，64位整型
+    #[serde(rename = "MsgId", deserialize_with = "int")]
+    pub msg_id: i64,
+}
+
+/// 链接消息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 消息标题
+    #[serde(rename = "Title")]
+    pub title: String,
+    /// 消息描述
+    #[serde(rename = "Description")]
+    pub description: String,
+    /// 消息链接
+    #[serde(rename = "Url")]
+    pub url: String,
+    /// 消息id，64位整型
+    #[serde(rename = "MsgId", deserialize_with = "int")]
+    pub msg_id: i64,
+}
+
+/// 未识别的事件推送，保留全部字段
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventMessage {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 事件类型
+    #[serde(rename = "Event")]
+    pub event: String,
+    /// 其他事件字段
+    #[serde(flatten)]
+    pub fields: Map<String, Value>,
+}
+
+/// 关注事件，扫描带参数二维码关注时EventKey为qrscene_前缀的场景值
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 事件KEY值，qrscene_为前缀，后面为二维码的参数值
+    #[serde(rename = "EventKey", default)]
+    pub event_key: Option<String>,
+    /// 二维码的ticket，可用来换取二维码图片
+    #[serde(rename = "Ticket", default)]
+    pub ticket: Option<String>,
+}
+
+impl SubscribeEvent {
+    /// 带参数二维码的场景值，普通关注时返回None
+    pub fn scene(&self) -> Option<&str> {
+        self.event_key
+            .as_deref()
+            .and_then(|key| key.strip_prefix("qrscene_"))
+    }
+}
+
+/// 已关注用户扫描带参数二维码事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 事件KEY值，二维码的场景值
+    #[serde(rename = "EventKey")]
+    pub event_key: String,
+    /// 二维码的ticket
+    #[serde(rename = "Ticket", default)]
+    pub ticket: Option<String>,
+}
+
+/// 自定义菜单事件，CLICK时EventKey为菜单KEY值，VIEW时EventKey为跳转URL
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MenuEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 事件KEY值
+    #[serde(rename = "EventKey")]
+    pub event_key: String,
+    /// 指菜单ID，如果是个性化菜单，则可以通过这个字段，知道是哪个规则的菜单被点击了
+    #[serde(rename = "MenuId", default)]
+    pub menu_id: Option<String>,
+}
+
+/// 上报地理位置事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 地理位置纬度
+    #[serde(rename = "Latitude", deserialize_with = "float")]
+    pub latitude: f64,
+    /// 地理位置经度
+    #[serde(rename = "Longitude", deserialize_with = "float")]
+    pub longitude: f64,
+    /// 地理位置精度
+    #[serde(rename = "Precision", deserialize_with = "float")]
+    pub precision: f64,
+}
+
+/// 扫码推事件(scancode_push、scancode_waitmsg)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCodeEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 菜单KEY值
+    #[serde(rename = "EventKey")]
+    pub event_key: String,
+    /// 扫描信息
+    #[serde(rename = "ScanCodeInfo")]
+    pub scan_code_info: ScanCodeInfo,
+}
+
+/// 扫描信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanCodeInfo {
+    /// 扫描类型，一般是qrcode
+    #[serde(rename = "ScanType")]
+    pub scan_type: String,
+    /// 扫描结果，即二维码对应的字符串信息
+    #[serde(rename = "ScanResult")]
+    pub scan_result: String,
+}
+
+/// 弹出发图器的事件(pic_sysphoto、pic_photo_or_album、pic_weixin)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PicEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 菜单KEY值
+    #[serde(rename = "EventKey")]
+    pub event_key: String,
+    /// 发送的图片信息
+    #[serde(rename = "SendPicsInfo")]
+    pub send_pics_info: SendPicsInfo,
+}
+
+/// 发送的图片信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendPicsInfo {
+    /// 发送的图片数量
+    #[serde(rename = "Count", deserialize_with = "int")]
+    pub count: i64,
+    /// 图片列表
+    #[serde(rename = "PicList", default)]
+    pub pic_list: PicList,
+}
+
+/// 图片列表，XML中为多个item元素
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PicList {
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub item: Vec<PicItem>,
+}
+
+/// 图片信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PicItem {
+    /// 图片的MD5值，开发者若需要，可用于验证接收到图片
+    #[serde(rename = "PicMd5Sum")]
+    pub pic_md5_sum: String,
+}
+
+/// 弹出地理位置选择器的事件(location_select)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocationSelectEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 菜单KEY值
+    #[serde(rename = "EventKey")]
+    pub event_key: String,
+    /// 发送的位置信息
+    #[serde(rename = "SendLocationInfo")]
+    pub send_location_info: SendLocationInfo,
+}
+
+/// 发送的位置信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SendLocationInfo {
+    /// 地理位置纬度
+    #[serde(rename = "Location_X", deserialize_with = "float")]
+    pub location_x: f64,
+    /// 地理位置经度
+    #[serde(rename = "Location_Y", deserialize_with = "float")]
+    pub location_y: f64,
+    /// 精度，可理解为精度或者比例尺、越精细的话 scale越高
+    #[serde(rename = "Scale", deserialize_with = "int")]
+    pub scale: i64,
+    /// 地理位置的字符串信息
+    #[serde(rename = "Label", default)]
+    pub label: String,
+    /// 朋友圈POI的名字，可能为空
+    #[serde(rename = "Poiname", default)]
+    pub poiname: Option<String>,
+}
+
+/// 模板消息发送任务完成事件
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateSendJobFinishEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 消息id
+    #[serde(rename = "MsgID", deserialize_with = "int")]
+    pub msg_id: i64,
+    /// 发送状态，success为成功，failed:user block为用户拒收，failed: system failed为其他原因失败
+    #[serde(rename = "Status")]
+    pub status: String,
+}
+
+/// 用户操作订阅通知弹窗的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeMsgPopupItem {
+    /// 模板id
+    #[serde(rename = "TemplateId")]
+    pub template_id: String,
+    /// 用户点击行为，accept同意，reject拒绝
+    #[serde(rename = "SubscribeStatusString")]
+    pub subscribe_status_string: String,
+    /// 场景，0:小程序或H5页面，1:图文消息，2:公众号或服务号会话
+    #[serde(rename = "PopupScene", default)]
+    pub popup_scene: Option<String>,
+}
+
+/// 用户在订阅通知管理页修改订阅的结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeMsgChangeItem {
+    /// 模板id
+    #[serde(rename = "TemplateId")]
+    pub template_id: String,
+    /// 用户点击行为，reject拒绝，仅推送用户拒收通知
+    #[serde(rename = "SubscribeStatusString")]
+    pub subscribe_status_string: String,
+}
+
+/// 订阅通知发送结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeMsgSentItem {
+    /// 模板id
+    #[serde(rename = "TemplateId")]
+    pub template_id: String,
+    /// 消息id
+    #[serde(rename = "MsgID")]
+    pub msg_id: String,
+    /// 推送结果状态码，0表示成功
+    #[serde(rename = "ErrorCode", deserialize_with = "int")]
+    pub error_code: i64,
+    /// 推送结果状态码对应的含义
+    #[serde(rename = "ErrorStatus")]
+    pub error_status: String,
+}
+
+/// 订阅通知事件，公众号XML格式的列表在`SubscribeMsg*Event.List`中，小程序JSON格式的列表在`List`中
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeMsgEvent<T> {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 订阅结果列表
+    pub list: Vec<T>,
+}
+
+/// 事件推送
+#[derive(Debug, Clone)]
+pub enum InboundEvent {
+    /// 关注(subscribe)
+    Subscribe(SubscribeEvent),
+    /// 取消关注(unsubscribe)
+    Unsubscribe(MessageHeader),
+    /// 已关注用户扫描带参数二维码(SCAN)
+    Scan(ScanEvent),
+    /// 点击菜单拉取消息(CLICK)
+    Click(MenuEvent),
+    /// 点击菜单跳转链接(VIEW)
+    View(MenuEvent),
+    /// 点击菜单跳转小程序(view_miniprogram)，EventKey为小程序路径
+    ViewMiniprogram(MenuEvent),
+    /// 扫码推事件(scancode_push)
+    ScancodePush(ScanCodeEvent),
+    /// 扫码推事件且弹出"消息接收中"提示框(scancode_waitmsg)
+    ScancodeWaitmsg(ScanCodeEvent),
+    /// 弹出系统拍照发图(pic_sysphoto)
+    PicSysphoto(PicEvent),
+    /// 弹出拍照或者相册发图(pic_photo_or_album)
+    PicPhotoOrAlbum(PicEvent),
+    /// 弹出微信相册发图器(pic_weixin)
+    PicWeixin(PicEvent),
+    /// 弹出地理位置选择器(location_select)
+    LocationSelect(LocationSelectEvent),
+    /// 上报地理位置(LOCATION)
+    Location(LocationEvent),
+    /// 模板消息发送任务完成(TEMPLATESENDJOBFINISH)
+    TemplateSendJobFinish(TemplateSendJobFinishEvent),
+    /// 用户操作订阅通知弹窗(subscribe_msg_popup_event)
+    SubscribeMsgPopup(SubscribeMsgEvent<SubscribeMsgPopupItem>),
+    /// 用户管理订阅通知(subscribe_msg_change_event)
+    SubscribeMsgChange(SubscribeMsgEvent<SubscribeMsgChangeItem>),
+    /// 发送订阅通知(subscribe_msg_sent_event)
+    SubscribeMsgSent(SubscribeMsgEvent<SubscribeMsgSentItem>),
+    /// 音视频内容安全识别结果(wxa_media_check)
+    WxaMediaCheck(MediaCheckEvent),
+    /// 未识别的事件
+    Other(EventMessage),
+}
+
+impl InboundEvent {
+    /// 从字段表解析事件
+    pub fn from_map(header: MessageHeader, map: Map<String, Value>) -> RPayResult<Self> {
+        let event = map.get("Event").and_then(|v| v.as_str()).unwrap_or_default();
+        let event = match event.to_ascii_lowercase().as_str() {
+            "subscribe" => InboundEvent::Subscribe(from_map(&map)?),
+            "unsubscribe" => InboundEvent::Unsubscribe(header),
+            "scan" => InboundEvent::Scan(from_map(&map)?),
+            "click" => InboundEvent::Click(from_map(&map)?),
+            "view" => InboundEvent::View(from_map(&map)?),
+            "view_miniprogram" => InboundEvent::ViewMiniprogram(from_map(&map)?),
+            "scancode_push" => InboundEvent::ScancodePush(from_map(&map)?),
+            "scancode_waitmsg" => InboundEvent::ScancodeWaitmsg(from_map(&map)?),
+            "pic_sysphoto" => InboundEvent::PicSysphoto(from_map(&map)?),
+            "pic_photo_or_album" => InboundEvent::PicPhotoOrAlbum(from_map(&map)?),
+            "pic_weixin" => InboundEvent::PicWeixin(from_map(&map)?),
+            "location_select" => InboundEvent::LocationSelect(from_map(&map)?),
+            "location" => InboundEvent::Location(from_map(&map)?),
+            "templatesendjobfinish" => InboundEvent::TemplateSendJobFinish(from_map(&map)?),
+            "subscribe_msg_popup_event" => {
+                InboundEvent::SubscribeMsgPopup(subscribe_msg_event(header, &map, "SubscribeMsgPopupEvent")?)
+            }
+            "subscribe_msg_change_event" => {
+                InboundEvent::SubscribeMsgChange(subscribe_msg_event(header, &map, "SubscribeMsgChangeEvent")?)
+            }
+            "subscribe_msg_sent_event" => {
+                InboundEvent::SubscribeMsgSent(subscribe_msg_event(header, &map, "SubscribeMsgSentEvent")?)
+            }
+            "wxa_media_check" => InboundEvent::WxaMediaCheck(from_map(&map)?),
+            _ => InboundEvent::Other(from_map(&map)?),
+        };
+        Ok(event)
+    }
+
+    /// 消息公共字段
+    pub fn header(&self) -> &MessageHeader {
+        match self {
+            InboundEvent::Subscribe(event) => &event.header,
+            InboundEvent::Unsubscribe(header) => header,
+            InboundEvent::Scan(event) => &event.header,
+            InboundEvent::Click(event) | InboundEvent::View(event) | InboundEvent::ViewMiniprogram(event) => {
+                &event.header
+            }
+            InboundEvent::ScancodePush(event) | InboundEvent::ScancodeWaitmsg(event) => &event.header,
+            InboundEvent::PicSysphoto(event) | InboundEvent::PicPhotoOrAlbum(event) | InboundEvent::PicWeixin(event) => {
+                &event.header
+            }
+            InboundEvent::LocationSelect(event) => &event.header,
+            InboundEvent::Location(event) => &event.header,
+            InboundEvent::TemplateSendJobFinish(event) => &event.header,
+            InboundEvent::SubscribeMsgPopup(event) => &event.header,
+            InboundEvent::SubscribeMsgChange(event) => &event.header,
+            InboundEvent::SubscribeMsgSent(event) => &event.header,
+            InboundEvent::WxaMediaCheck(event) => &event.header,
+            InboundEvent::Other(event) => &event.header,
+        }
+    }
+}
+
+/// 接收的普通消息和事件推送
+#[derive(Debug, Clone)]
+pub enum InboundMessage {
+    /// 文本消息
+    Text(TextMessage),
+    /// 图片消息
+    Image(ImageMessage),
+    /// 语音消息
+    Voice(VoiceMessage),
+    /// 视频消息
+    Video(VideoMessage),
+    /// 小视频消息
+    ShortVideo(VideoMessage),
+    /// 地理位置消息
+    Location(LocationMessage),
+    /// 链接消息
+    Link(LinkMessage),
+    /// 事件推送
+    Event(InboundEvent),
+    /// 未识别的消息，保留全部字段
+    Unknown(MessageHeader, Map<String, Value>),
+}
+
+impl InboundMessage {
+    /// 从字段表解析消息
+    pub fn from_map(map: Map<String, Value>) -> RPayResult<Self> {
+        let header: MessageHeader = from_map(&map)?;
+        let message = match header.msg_type.as_str() {
+            "text" => InboundMessage::Text(from_map(&map)?),
+            "image" => InboundMessage::Image(from_map(&map)?),
+            "voice" => InboundMessage::Voice(from_map(&map)?),
+            "video" => InboundMessage::Video(from_map(&map)?),
+            "shortvideo" => InboundMessage::ShortVideo(from_map(&map)?),
+            "location" => InboundMessage::Location(from_map(&map)?),
+            "link" => InboundMessage::Link(from_map(&map)?),
+            "event" => InboundMessage::Event(InboundEvent::from_map(header, map)?),
+            _ => InboundMessage::Unknown(header, map),
+        };
+        Ok(message)
+    }
+
+    /// 消息公共字段
+    pub fn header(&self) -> &MessageHeader {
+        match self {
+            InboundMessage::Text(message) => &message.header,
+            InboundMessage::Image(message) => &message.header,
+            InboundMessage::Voice(message) => &message.header,
+            InboundMessage::Video(message) | InboundMessage::ShortVideo(message) => &message.header,
+            InboundMessage::Location(message) => &message.header,
+            InboundMessage::Link(message) => &message.header,
+            InboundMessage::Event(event) => event.header(),
+            InboundMessage::Unknown(header, _) => header,
+        }
+    }
+}
+
+// 订阅通知事件列表，单条记录时XML解析为对象，统一转换为数组
+fn subscribe_msg_event<T: DeserializeOwned>(
+    header: MessageHeader,
+    map: &Map<String, Value>,
+    name: &str,
+) -> RPayResult<SubscribeMsgEvent<T>> {
+    let list = map
+        .get(name)
+        .and_then(|v| v.get("List"))
+        .or_else(|| map.get("List"))
+        .cloned()
+        .unwrap_or(Value::Array(Vec::new()));
+    let list = match list {
+        Value::Array(values) => values,
+        value => vec![value],
+    };
+    let list = list
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<Result<Vec<T>, _>>()?;
+    Ok(SubscribeMsgEvent { header, list })
+}
+
+fn from_map<T: DeserializeOwned>(map: &Map<String, Value>) -> RPayResult<T> {
+    Ok(serde_json::from_value(Value::Object(map.clone()))?)
+}
+
+/// XML中重复的元素解析为数组，单个元素解析为对象，统一为列表
+pub(crate) fn one_or_many<'de, D: Deserializer<'de>, T: DeserializeOwned>(deserializer: D) -> Result<Vec<T>, D::Error> {
+    let values = match Value::deserialize(deserializer)? {
+        Value::Array(values) => values,
+        Value::Null => Vec::new(),
+        value => vec![value],
+    };
+    values
+        .into_iter()
+        .map(serde_json::from_value)
+        .collect::<Result<Vec<T>, _>>()
+        .map_err(serde::de::Error::custom)
+}
+
+/// XML中的数值均为字符串，兼容字符串和数字两种格式
+pub(crate) fn float<'de, D: Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(number) => number
+            .as_f64()
+            .ok_or_else(|| serde::de::Error::custom(format!("无效的数值: {}", number))),
+        Value::String(text) => text.trim().parse().map_err(serde::de::Error::custom),
+        other => Err(serde::de::Error::custom(format!("无效的数值: {}", other))),
+    }
+}
+
+/// XML中的数值均为字符串，兼容字符串和数字两种格式
+pub(crate) fn int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<i64, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Number(number) => number
+            .as_i64()
+            .ok_or_else(|| serde::de::Error::custom(format!("无效的整数: {}", number))),
+        Value::String(text) => text.trim().parse().map_err(serde::de::Error::custom),
+        other => Err(serde::de::Error::custom(format!("无效的整数: {}", other))),
+    }
+}
+
+/// 可选的整数，兼容字符串和数字两种格式，空字符串视为None
+pub(crate) fn opt_int<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<i64>, D::Error> {
+    match Value::deserialize(deserializer)? {
+        Value::Null => Ok(None),
+        Value::String(text) if text.trim().is_empty() => Ok(None),
+        value => int(value).map(Some).map_err(serde::de::Error::custom),
+    }
+}
+
+/// 解析消息推送的XML，返回根元素下的字段表
+pub fn xml_to_map(xml: &str) -> RPayResult<Map<String, Value>> {
+    let mut reader = Reader::from_str(xml);
+    reader.trim_text(true);
+    // (元素名, 子元素, 文本)
+    let mut stack: Vec<(String, Map<String, Value>, String)> = Vec::new();
+    loop {
+        match reader.read_event()? {
+            Event::Start(start) => {
+                let name = String::from_utf8(start.name().as_ref().to_vec())?;
+                stack.push((name, Map::new(), String::new()));
+            }
+            Event::Empty(empty) => {
+                let name = String::from_utf8(empty.name().as_ref().to_vec())?;
+                if let Some((_, children, _)) = stack.last_mut() {
+                    insert(children, name, Value::String(String::new()));
+                }
+            }
+            Event::Text(text) => {
+                if let Some((_, _, content)) = stack.last_mut() {
+                    content.push_str(&text.unescape()?);
+                }
+            }
+            Event::CData(cdata) => {
+                if let Some((_, _, content)) = stack.last_mut() {
+                    content.push_str(&String::from_utf8(cdata.into_inner().to_vec())?);
+                }
+            }
+            Event::End(_) => {
+                let Some((name, children, content)) = stack.pop() else {
+                    break;
+                };
+                let value = match children.is_empty() {
+                    true => Value::String(content),
+                    false => Value::Object(children),
+                };
+                match stack.last_mut() {
+                    Some((_, parent, _)) => insert(parent, name, value),
+                    None => {
+                        return match value {
+                            Value::Object(map) => Ok(map),
+                            _ => Ok(Map::new()),
+                        }
+                    }
+                }
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Err(RPayError::ErrorWithMsg(String::from("XML格式错误")))
+}
+
+// 重复出现的元素合并为数组
+fn insert(map: &mut Map<String, Value>, name: String, value: Value) {
+    match map.get_mut(&name) {
+        Some(Value::Array(values)) => values.push(value),
+        Some(existing) => {
+            let first = existing.take();
+            *existing = Value::Array(vec![first, value]);
+        }
+        None => {
+            map.insert(name, value);
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayError, RPayResult,
+};
+
+/// 客服帐号管理
+/// https://developers.weixin.qq.com/doc/offiaccount/Customer_Service/Customer_Service_Management.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct KfAccounts {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl KfAccounts {
+    /// 添加客服帐号，kf_account格式为：帐号前缀@公众号微信号，帐号前缀最多10个字符
+    pub async fn add(&self, kf_account: &str, nickname: &str) -> RPayResult<KfResponse> {
+        self.post_request("/customservice/kfaccount/add", json!({ "kf_account": kf_account, "nickname": nickname }))?
+            .send()
+            .await
+    }
+
+    /// 添加客服帐号(同步)
+    #[cfg(feature = "blocking")]
+    pub fn add_blocking(&self, kf_account: &str, nickname: &str) -> RPayResult<KfResponse> {
+        self.post_request("/customservice/kfaccount/add", json!({ "kf_account": kf_account, "nickname": nickname }))?
+            .send_blocking()
+    }
+
+    /// 设置客服昵称
+    pub async fn update(&self, kf_account: &str, nickname: &str) -> RPayResult<KfResponse> {
+        self.post_request("/customservice/kfaccount/update", json!({ "kf_account": kf_account, "nickname": nickname }))?
+            .send()
+            .await
+    }
+
+    /// 设置客服昵称(同步)
+    #[cfg(feature = "blocking")]
+    pub fn update_blocking(&self, kf_account: &str, nickname: &str) -> RPayResult<KfResponse> {
+        self.post_request("/customservice/kfaccount/update", json!({ "kf_account": kf_account, "nickname": nickname }))?
+            .send_blocking()
+    }
+
+    /// 删除客服帐号
+    pub async fn delete(&self, kf_account: &str) -> RPayResult<KfResponse> {
+        self.delete_request(kf_account)?.send().await
+    }
+
+    /// 删除客服帐号(同步)
+    #[cfg(feature = "blocking")]
+    pub fn delete_blocking(&self, kf_account: &str) -> RPayResult<KfResponse> {
+        self.delete_request(kf_account)?.send_blocking()
+    }
+
+    /// 邀请绑定客服帐号，新添加的客服帐号不能直接使用，需要邀请个人微信号绑定后才可登录
+    pub async fn invite(&self, kf_account: &str, invite_wx: &str) -> RPayResult<KfResponse> {
+        self.post_request("/customservice/kfaccount/inviteworker", json!({ "kf_account": kf_account, "invite_wx": invite_wx }))?
+            .send()
+            .await
+    }
+
+    /// 邀请绑定客服帐号(同步)
+    #[cfg(feature = "blocking")]
+    pub fn invite_blocking(&self, kf_account: &str, invite_wx: &str) -> RPayResult<KfResponse> {
+        self.post_request("/customservice/kfaccount/inviteworker", json!({ "kf_account": kf_account, "invite_wx": invite_wx }))?
+            .send_blocking()
+    }
+
+    /// 获取所有客服帐号
+    pub async fn list(&self) -> RPayResult<KfListResponse> {
+        self.get_request("/cgi-bin/customservice/getkflist")?.send().await
+    }
+
+    /// 获取所有客服帐号(同步)
+    #[cfg(feature = "blocking")]
+    pub fn list_blocking(&self) -> RPayResult<KfListResponse> {
+        self.get_request("/cgi-bin/customservice/getkflist")?.send_blocking()
+    }
+
+    /// 获取在线客服
+    pub async fn list_online(&self) -> RPayResult<KfOnlineListResponse> {
+        self.get_request("/cgi-bin/customservice/getonlinekflist")?.send().await
+    }
+
+    /// 获取在线客服(同步)
+    #[cfg(feature = "blocking")]
+    pub fn list_online_blocking(&self) -> RPayResult<KfOnlineListResponse> {
+        self.get_request("/cgi-bin/customservice/getonlinekflist")?.send_blocking()
+    }
+
+    fn delete_request(&self, kf_account: &str) -> RPayResult<Request> {
+        let url = reqwest::Url::parse_with_params(
+            "https://api.weixin.qq.com/customservice/kfaccount/del",
+            &[("access_token", self.access_token.as_str()), ("kf_account", kf_account)],
+        )
+        .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))?;
+        Ok(RequestBuilder::default().url(url.to_string()).build()?)
+    }
+
+    fn get_request(&self, path: &str) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+
+    fn post_request(&self, path: &str, body: serde_json::Value) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(body.to_string())
+            .build()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KfResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+}
+
+/// 客服帐号信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KfInfo {
+    /// 完整客服帐号，格式为：帐号前缀@公众号微信号
+    pub kf_account: String,
+    /// 客服昵称
+    pub kf_nick: String,
+    /// 客服编号
+    pub kf_id: String,
+    /// 客服头像
+    pub kf_headimgurl: Option<String>,
+    /// 如果客服帐号已绑定了客服人员微信号，则此处显示微信号
+    pub kf_wx: Option<String>,
+    /// 如果客服帐号尚未绑定微信号，但是已经发起了一个绑定邀请，则此处显示绑定邀请的微信号
+    pub invite_wx: Option<String>,
+    /// 绑定邀请的过期时间，为unix时间戳
+    pub invite_expire_time: Option<i64>,
+    /// 邀请的状态，有等待确认"waiting"，被拒绝"rejected"，过期"expired"
+    pub invite_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KfListResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 客服帐号列表
+    #[serde(default)]
+    pub kf_list: Vec<KfInfo>,
+}
+
+/// 在线客服信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KfOnlineInfo {
+    /// 完整客服帐号
+    pub kf_account: String,
+    /// 客服在线状态，目前为：1、web 在线
+    pub status: i64,
+    /// 客服编号
+    pub kf_id: String,
+    /// 客服当前正在接待的会话数
+    pub accepted_case: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KfOnlineListResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 在线客服列表
+    #[serde(default)]
+    pub kf_online_list: Vec<KfOnlineInfo>,
+}
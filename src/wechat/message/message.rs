@@ -1,11 +1,10 @@
 use std::collections::HashMap;
 
 use derive_builder::Builder;
-use reqwest::Client;
 use serde::{ Deserialize, Serialize};
 use serde_json::Value;
 
-use crate::{core::common::Lang, RPayResult};
+use crate::{common::HttpMethod, core::{common::Lang, request::{Request, RequestBuilder}}, RPayResult};
 
 /*
  {
@@ -90,16 +89,24 @@ impl Message {
     // send发送订阅通知
     // https://developers.weixin.qq.com/doc/offiaccount/Subscription_Messages/api.html#send发送订阅通知
     pub async fn send(&mut self) -> RPayResult<Response> {
+        let resp = self.send_request()?.send::<Response>().await?;
+        Ok(resp)
+    }
+
+    /// 发送订阅通知(同步)
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(&mut self) -> RPayResult<Response> {
+        self.send_request()?.send_blocking::<Response>()
+    }
+
+    fn send_request(&self) -> RPayResult<Request> {
         let url = format!("https://api.weixin.qq.com/cgi-bin/message/subscribe/send?access_token={}", self.access_token);
-        let json_body = serde_json::to_string(self).unwrap();
-        let resp = Client::new()
-            .post(url)
+        let json_body = serde_json::to_string(self)?;
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
             .body(json_body)
-            .send()
-            .await?
-            .json::<Response>()
-            .await?;
-        Ok(resp)
+            .build()?)
     }
 }
 
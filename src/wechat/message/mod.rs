@@ -1,3 +1,10 @@
 pub mod message;
 pub mod customer_service;
+pub mod dispatcher;
+pub mod inbound;
+pub mod kf_account;
+pub mod push;
+pub mod reply;
+pub mod subscribe;
+pub mod template_message;
 pub use customer_service::*;
\ No newline at end of file
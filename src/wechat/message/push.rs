@@ -0,0 +1,200 @@
+use aes::Aes256;
+use base64::{
+    alphabet,
+    engine::{GeneralPurpose, GeneralPurposeConfig},
+    Engine,
+};
+use block_modes::{block_padding::NoPadding, BlockMode, Cbc};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{utils, RPayError, RPayResult};
+
+use super::inbound::{InboundMessage, PushFormat};
+
+type Aes256Cbc = Cbc<Aes256, NoPadding>;
+
+// 消息体补位的块大小
+const BLOCK_SIZE: usize = 32;
+
+/// 消息推送请求的URL参数
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct PushQuery {
+    /// 签名 sha1(sort(token, timestamp, nonce))
+    pub signature: String,
+    /// 时间戳
+    pub timestamp: String,
+    /// 随机数
+    pub nonce: String,
+    /// 随机字符串，仅服务器地址验证时提供
+    pub echostr: Option<String>,
+    /// 发送方openid
+    pub openid: Option<String>,
+    /// 加密类型，安全模式和兼容模式为aes
+    pub encrypt_type: Option<String>,
+    /// 消息体签名 sha1(sort(token, timestamp, nonce, encrypt))
+    pub msg_signature: Option<String>,
+}
+
+impl PushQuery {
+    /// 消息是否加密
+    pub fn is_encrypted(&self) -> bool {
+        self.encrypt_type.as_deref() == Some("aes")
+    }
+}
+
+/// 消息推送服务，用于服务器地址验证、消息签名校验和安全模式消息加解密
+/// https://developers.weixin.qq.com/doc/offiaccount/Message_Management/Message_encryption_and_decryption_instructions.html
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct PushServer {
+    /// 服务器配置中的令牌(Token)
+    #[builder(setter(into))]
+    pub token: String,
+    /// 消息加解密密钥(EncodingAESKey)，43位字符，安全模式和兼容模式必填
+    #[builder(default, setter(strip_option, into))]
+    pub encoding_aes_key: Option<String>,
+    /// 公众号或小程序的appid，解密时校验消息中的appid
+    #[builder(setter(into))]
+    pub app_id: String,
+}
+
+impl PushServer {
+    /// 校验请求签名
+    pub fn check_signature(&self, signature: &str, timestamp: &str, nonce: &str) -> bool {
+        self.signature(&[timestamp, nonce]) == signature
+    }
+
+    /// 服务器地址验证，签名正确时返回需要原样响应的echostr
+    pub fn verify_url(&self, query: &PushQuery) -> RPayResult<String> {
+        self.verify(query)?;
+        query
+            .echostr
+            .clone()
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("缺少echostr参数")))
+    }
+
+    /// 校验签名并解析消息，加密消息先校验msg_signature再解密
+    pub fn parse(&self, query: &PushQuery, body: &str) -> RPayResult<InboundMessage> {
+        let plain_text = self.decrypt_body(query, body)?;
+        let format = PushFormat::detect(&plain_text);
+        InboundMessage::from_map(format.parse(&plain_text)?)
+    }
+
+    /// 校验签名，加密消息校验msg_signature后返回解密的明文，明文消息原样返回
+    pub fn decrypt_body(&self, query: &PushQuery, body: &str) -> RPayResult<String> {
+        self.verify(query)?;
+        if !query.is_encrypted() {
+            return Ok(body.to_string());
+        }
+        let fields = PushFormat::detect(body).parse(body)?;
+        let encrypt = fields
+            .get("Encrypt")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("加密消息缺少Encrypt字段")))?;
+        let msg_signature = query.msg_signature.as_deref().unwrap_or_default();
+        if self.signature(&[&query.timestamp, &query.nonce, encrypt]) != msg_signature {
+            return Err(RPayError::ErrorWithMsg(String::from("消息体签名校验失败")));
+        }
+        self.decrypt(encrypt)
+    }
+
+    /// 解密消息体，并校验appid
+    pub fn decrypt(&self, encrypt: &str) -> RPayResult<String> {
+        let key = self.aes_key()?;
+        let data = utils::base64_decode(encrypt)?;
+        let cipher = Aes256Cbc::new_from_slices(&key, &key[..16])
+            .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))?;
+        let mut plain = cipher
+            .decrypt_vec(&data)
+            .map_err(|err| RPayError::ErrorWithMsg(format!("消息解密失败:{}", err)))?;
+        let pad = plain.last().copied().unwrap_or_default() as usize;
+        if pad == 0 || pad > BLOCK_SIZE || pad > plain.len() {
+            return Err(RPayError::ErrorWithMsg(String::from("消息补位错误")));
+        }
+        plain.truncate(plain.len() - pad);
+        // 16字节随机串 + 4字节消息长度(网络字节序) + 消息 + appid
+        if plain.len() < 20 {
+            return Err(RPayError::ErrorWithMsg(String::from("消息长度错误")));
+        }
+        let len = u32::from_be_bytes([plain[16], plain[17], plain[18], plain[19]]) as usize;
+        let (message, app_id) = plain[20..]
+            .split_at_checked(len)
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("消息长度错误")))?;
+        if app_id != self.app_id.as_bytes() {
+            return Err(RPayError::ErrorWithMsg(format!(
+                "appid校验失败: {}",
+                String::from_utf8_lossy(app_id)
+            )));
+        }
+        Ok(String::from_utf8(message.to_vec())?)
+    }
+
+    /// 加密消息体，返回base64编码的密文
+    pub fn encrypt(&self, message: &str) -> RPayResult<String> {
+        let key = self.aes_key()?;
+        let mut plain = Vec::with_capacity(message.len() + self.app_id.len() + BLOCK_SIZE + 20);
+        plain.extend_from_slice(&utils::get_nonce_str().as_bytes()[..16]);
+        plain.extend_from_slice(&(message.len() as u32).to_be_bytes());
+        plain.extend_from_slice(message.as_bytes());
+        plain.extend_from_slice(self.app_id.as_bytes());
+        let pad = BLOCK_SIZE - plain.len() % BLOCK_SIZE;
+        plain.resize(plain.len() + pad, pad as u8);
+        let cipher = Aes256Cbc::new_from_slices(&key, &key[..16])
+            .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))?;
+        Ok(utils::base64_encode(cipher.encrypt_vec(&plain)))
+    }
+
+    /// 加密被动回复消息，生成包含Encrypt、MsgSignature、TimeStamp、Nonce的响应内容
+    pub fn encrypt_reply(&self, reply: &str, format: PushFormat, timestamp: &str, nonce: &str) -> RPayResult<String> {
+        let encrypt = self.encrypt(reply)?;
+        let msg_signature = self.signature(&[timestamp, nonce, &encrypt]);
+        let body = match format {
+            PushFormat::Xml => format!(
+                "<xml><Encrypt><![CDATA[{}]]></Encrypt><MsgSignature><![CDATA[{}]]></MsgSignature><TimeStamp>{}</TimeStamp><Nonce><![CDATA[{}]]></Nonce></xml>",
+                encrypt, msg_signature, timestamp, nonce
+            ),
+            PushFormat::Json => json!({
+                "Encrypt": encrypt,
+                "MsgSignature": msg_signature,
+                "TimeStamp": timestamp,
+                "Nonce": nonce,
+            })
+            .to_string(),
+        };
+        Ok(body)
+    }
+
+    fn verify(&self, query: &PushQuery) -> RPayResult<()> {
+        match self.check_signature(&query.signature, &query.timestamp, &query.nonce) {
+            true => Ok(()),
+            false => Err(RPayError::ErrorWithMsg(String::from("消息签名校验失败"))),
+        }
+    }
+
+    // token和参数按字典序排序后拼接，取SHA1
+    fn signature(&self, params: &[&str]) -> String {
+        let mut values = vec![self.token.as_str()];
+        values.extend_from_slice(params);
+        values.sort_unstable();
+        utils::sha1_hex(values.concat())
+    }
+
+    // EncodingAESKey补齐"="后base64解码，得到32字节AES密钥，最后一个字符的低位不一定为0
+    fn aes_key(&self) -> RPayResult<Vec<u8>> {
+        let encoding_aes_key = self
+            .encoding_aes_key
+            .as_deref()
+            .ok_or_else(|| RPayError::ErrorWithMsg(String::from("未配置EncodingAESKey")))?;
+        let engine = GeneralPurpose::new(
+            &alphabet::STANDARD,
+            GeneralPurposeConfig::new().with_decode_allow_trailing_bits(true),
+        );
+        let key = engine.decode(format!("{}=", encoding_aes_key))?;
+        if key.len() != 32 {
+            return Err(RPayError::ErrorWithMsg(String::from("EncodingAESKey长度错误")));
+        }
+        Ok(key)
+    }
+}
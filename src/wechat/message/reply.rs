@@ -0,0 +1,270 @@
+use derive_builder::Builder;
+use serde_json::{json, Map, Value};
+
+use crate::{utils, RPayResult};
+
+use super::{
+    inbound::{MessageHeader, PushFormat},
+    push::{PushQuery, PushServer},
+};
+
+/// 被动回复消息，在收到消息推送的5秒内直接作为响应返回，不占用接口调用次数
+/// https://developers.weixin.qq.com/doc/offiaccount/Message_Management/Passive_user_reply_message.html
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct Reply {
+    /// 接收方帐号(收到的OpenID)
+    #[builder(setter(into))]
+    pub to_user_name: String,
+    /// 开发者微信号(公众号或小程序的原始ID)
+    #[builder(setter(into))]
+    pub from_user_name: String,
+    /// 消息创建时间(整型)，默认为当前时间
+    #[builder(default = "utils::get_timestamp_secs()")]
+    pub create_time: i64,
+    /// 回复内容
+    pub content: ReplyContent,
+}
+
+/// 被动回复的消息内容
+#[derive(Debug, Clone)]
+pub enum ReplyContent {
+    /// 文本消息
+    Text(String),
+    /// 图片消息，参数为通过素材管理接口上传多媒体文件得到的media_id
+    Image(String),
+    /// 语音消息，参数为通过素材管理接口上传多媒体文件得到的media_id
+    Voice(String),
+    /// 视频消息
+    Video(Video),
+    /// 音乐消息
+    Music(Music),
+    /// 图文消息，目前仅支持1条图文
+    News(Vec<Article>),
+    /// 将消息转发到客服，可指定客服帐号
+    TransferCustomerService(Option<String>),
+}
+
+/// 回复视频消息
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct Video {
+    /// 通过素材管理接口上传多媒体文件得到的media_id
+    #[builder(setter(into))]
+    pub media_id: String,
+    /// 视频消息的标题
+    #[builder(default, setter(strip_option, into))]
+    pub title: Option<String>,
+    /// 视频消息的描述
+    #[builder(default, setter(strip_option, into))]
+    pub description: Option<String>,
+}
+
+/// 回复音乐消息
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct Music {
+    /// 音乐标题
+    #[builder(default, setter(strip_option, into))]
+    pub title: Option<String>,
+    /// 音乐描述
+    #[builder(default, setter(strip_option, into))]
+    pub description: Option<String>,
+    /// 音乐链接
+    #[builder(default, setter(strip_option, into))]
+    pub music_url: Option<String>,
+    /// 高质量音乐链接，WIFI环境优先使用该链接播放音乐
+    #[builder(default, setter(strip_option, into))]
+    pub hq_music_url: Option<String>,
+    /// 缩略图的媒体id，通过素材管理接口上传多媒体文件得到的id
+    #[builder(setter(into))]
+    pub thumb_media_id: String,
+}
+
+/// 回复图文消息
+#[derive(Debug, Clone, Builder)]
+#[builder(pattern = "mutable")]
+pub struct Article {
+    /// 图文消息标题
+    #[builder(setter(into))]
+    pub title: String,
+    /// 图文消息描述
+    #[builder(default, setter(into))]
+    pub description: String,
+    /// 图片链接，支持JPG、PNG格式，较好的效果为大图360*200，小图200*200
+    #[builder(default, setter(into))]
+    pub pic_url: String,
+    /// 点击图文消息跳转链接
+    #[builder(setter(into))]
+    pub url: String,
+}
+
+impl Reply {
+    /// 回复收到的消息，收发方与收到的消息互换
+    pub fn to(header: &MessageHeader, content: ReplyContent) -> Self {
+        Reply {
+            to_user_name: header.from_user_name.clone(),
+            from_user_name: header.to_user_name.clone(),
+            create_time: utils::get_timestamp_secs(),
+            content,
+        }
+    }
+
+    /// 消息类型
+    pub fn msg_type(&self) -> &'static str {
+        match self.content {
+            ReplyContent::Text(_) => "text",
+            ReplyContent::Image(_) => "image",
+            ReplyContent::Voice(_) => "voice",
+            ReplyContent::Video(_) => "video",
+            ReplyContent::Music(_) => "music",
+            ReplyContent::News(_) => "news",
+            ReplyContent::TransferCustomerService(_) => "transfer_customer_service",
+        }
+    }
+
+    /// 生成XML格式的回复内容
+    pub fn to_xml(&self) -> String {
+        let mut xml = String::from("<xml>");
+        push_cdata(&mut xml, "ToUserName", &self.to_user_name);
+        push_cdata(&mut xml, "FromUserName", &self.from_user_name);
+        xml.push_str(&format!("<CreateTime>{}</CreateTime>", self.create_time));
+        push_cdata(&mut xml, "MsgType", self.msg_type());
+        match &self.content {
+            ReplyContent::Text(content) => push_cdata(&mut xml, "Content", content),
+            ReplyContent::Image(media_id) => {
+                xml.push_str("<Image>");
+                push_cdata(&mut xml, "MediaId", media_id);
+                xml.push_str("</Image>");
+            }
+            ReplyContent::Voice(media_id) => {
+                xml.push_str("<Voice>");
+                push_cdata(&mut xml, "MediaId", media_id);
+                xml.push_str("</Voice>");
+            }
+            ReplyContent::Video(video) => {
+                xml.push_str("<Video>");
+                push_cdata(&mut xml, "MediaId", &video.media_id);
+                push_option(&mut xml, "Title", &video.title);
+                push_option(&mut xml, "Description", &video.description);
+                xml.push_str("</Video>");
+            }
+            ReplyContent::Music(music) => {
+                xml.push_str("<Music>");
+                push_option(&mut xml, "Title", &music.title);
+                push_option(&mut xml, "Description", &music.description);
+                push_option(&mut xml, "MusicUrl", &music.music_url);
+                push_option(&mut xml, "HQMusicUrl", &music.hq_music_url);
+                push_cdata(&mut xml, "ThumbMediaId", &music.thumb_media_id);
+                xml.push_str("</Music>");
+            }
+            ReplyContent::News(articles) => {
+                xml.push_str(&format!("<ArticleCount>{}</ArticleCount><Articles>", articles.len()));
+                for article in articles {
+                    xml.push_str("<item>");
+                    push_cdata(&mut xml, "Title", &article.title);
+                    push_cdata(&mut xml, "Description", &article.description);
+                    push_cdata(&mut xml, "PicUrl", &article.pic_url);
+                    push_cdata(&mut xml, "Url", &article.url);
+                    xml.push_str("</item>");
+                }
+                xml.push_str("</Articles>");
+            }
+            ReplyContent::TransferCustomerService(kf_account) => {
+                if let Some(kf_account) = kf_account {
+                    xml.push_str("<TransInfo>");
+                    push_cdata(&mut xml, "KfAccount", kf_account);
+                    xml.push_str("</TransInfo>");
+                }
+            }
+        }
+        xml.push_str("</xml>");
+        xml
+    }
+
+    /// 生成JSON格式的回复内容，小程序消息推送选择JSON格式时使用
+    pub fn to_json(&self) -> String {
+        let mut map = Map::new();
+        map.insert(String::from("ToUserName"), json!(self.to_user_name));
+        map.insert(String::from("FromUserName"), json!(self.from_user_name));
+        map.insert(String::from("CreateTime"), json!(self.create_time));
+        map.insert(String::from("MsgType"), json!(self.msg_type()));
+        match &self.content {
+            ReplyContent::Text(content) => {
+                map.insert(String::from("Content"), json!(content));
+            }
+            ReplyContent::Image(media_id) => {
+                map.insert(String::from("Image"), json!({ "MediaId": media_id }));
+            }
+            ReplyContent::Voice(media_id) => {
+                map.insert(String::from("Voice"), json!({ "MediaId": media_id }));
+            }
+            ReplyContent::Video(video) => {
+                map.insert(
+                    String::from("Video"),
+                    json!({ "MediaId": video.media_id, "Title": video.title, "Description": video.description }),
+                );
+            }
+            ReplyContent::Music(music) => {
+                map.insert(
+                    String::from("Music"),
+                    json!({
+                        "Title": music.title,
+                        "Description": music.description,
+                        "MusicUrl": music.music_url,
+                        "HQMusicUrl": music.hq_music_url,
+                        "ThumbMediaId": music.thumb_media_id,
+                    }),
+                );
+            }
+            ReplyContent::News(articles) => {
+                let items: Vec<Value> = articles
+                    .iter()
+                    .map(|article| {
+                        json!({
+                            "Title": article.title,
+                            "Description": article.description,
+                            "PicUrl": article.pic_url,
+                            "Url": article.url,
+                        })
+                    })
+                    .collect();
+                map.insert(String::from("ArticleCount"), json!(items.len()));
+                map.insert(String::from("Articles"), Value::Array(items));
+            }
+            ReplyContent::TransferCustomerService(kf_account) => {
+                if let Some(kf_account) = kf_account {
+                    map.insert(String::from("TransInfo"), json!({ "KfAccount": kf_account }));
+                }
+            }
+        }
+        Value::Object(map).to_string()
+    }
+
+    /// 生成响应内容，推送消息为安全模式时加密回复
+    pub fn render(&self, server: &PushServer, query: &PushQuery, format: PushFormat) -> RPayResult<String> {
+        let body = match format {
+            PushFormat::Xml => self.to_xml(),
+            PushFormat::Json => self.to_json(),
+        };
+        match query.is_encrypted() {
+            true => server.encrypt_reply(&body, format, &query.timestamp, &query.nonce),
+            false => Ok(body),
+        }
+    }
+}
+
+// 内容中的"]]>"会提前结束CDATA，拆分到两个CDATA段中
+fn push_cdata(xml: &mut String, name: &str, value: &str) {
+    xml.push_str(&format!(
+        "<{0}><![CDATA[{1}]]></{0}>",
+        name,
+        value.replace("]]>", "]]]]><![CDATA[>")
+    ));
+}
+
+fn push_option(xml: &mut String, name: &str, value: &Option<String>) {
+    if let Some(value) = value {
+        push_cdata(xml, name, value);
+    }
+}
@@ -0,0 +1,263 @@
+use std::{collections::HashMap, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{template::PrivateTemplate, RPayError, RPayResult};
+
+use super::message::{DataItem, MessageBuilder};
+
+/// 订阅消息模板关键词的参数类型，关键词名称为类型加序号，如thing1、time2
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/mp-message-management/subscribe-message/sendMessage.html
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum KeywordRule {
+    /// 20个以内字符，可汉字、数字、字母或符号组合
+    Thing,
+    /// 32位以内数字，只能数字，可带小数
+    Number,
+    /// 32位以内字母，只能字母
+    Letter,
+    /// 5位以内符号，只能符号
+    Symbol,
+    /// 32位以内数字、字母或符号，可数字、字母或符号组合
+    CharacterString,
+    /// 24小时制时间格式(支持+年月日)，支持填时间段，两个时间点之间用"~"符号连接
+    Time,
+    /// 年月日格式(支持+24小时制时间)，支持填时间段，两个时间点之间用"~"符号连接
+    Date,
+    /// 1个币种符号+10位以内纯数字，可带小数，结尾可带"元"
+    Amount,
+    /// 17位以内，数字、符号，电话号码
+    PhoneNumber,
+    /// 8位以内，第一位与最后一位可为汉字，其余为字母或数字，车牌号码
+    CarNumber,
+    /// 10个以内纯汉字或20个以内纯字母或符号，中文和字母混合按中文名算
+    Name,
+    /// 5个以内纯汉字
+    Phrase,
+    /// 枚举值，只能使用模板中配置的枚举值
+    Enum,
+}
+
+impl FromStr for KeywordRule {
+    type Err = RPayError;
+
+    fn from_str(rule: &str) -> Result<Self, Self::Err> {
+        let rule = match rule {
+            "thing" => KeywordRule::Thing,
+            "number" => KeywordRule::Number,
+            "letter" => KeywordRule::Letter,
+            "symbol" => KeywordRule::Symbol,
+            "character_string" => KeywordRule::CharacterString,
+            "time" => KeywordRule::Time,
+            "date" => KeywordRule::Date,
+            "amount" => KeywordRule::Amount,
+            "phone_number" => KeywordRule::PhoneNumber,
+            "car_number" => KeywordRule::CarNumber,
+            "name" => KeywordRule::Name,
+            "phrase" => KeywordRule::Phrase,
+            "enum" => KeywordRule::Enum,
+            other => return Err(RPayError::ErrorWithMsg(format!("未知的关键词类型: {}", other))),
+        };
+        Ok(rule)
+    }
+}
+
+impl KeywordRule {
+    /// 根据关键词名称解析参数类型，如thing1解析为Thing
+    pub fn from_keyword(keyword: &str) -> RPayResult<Self> {
+        keyword.trim_end_matches(|c: char| c.is_ascii_digit()).parse()
+    }
+
+    /// 校验参数值，返回不符合规则的原因
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        if value.is_empty() {
+            return Err(String::from("不能为空"));
+        }
+        let len = value.chars().count();
+        let check = |ok: bool, rule: &str| match ok {
+            true => Ok(()),
+            false => Err(String::from(rule)),
+        };
+        match self {
+            KeywordRule::Thing => check(len <= 20, "20个以内字符"),
+            KeywordRule::Number => check(
+                len <= 32 && is_decimal(value),
+                "32位以内数字，只能数字，可带小数",
+            ),
+            KeywordRule::Letter => check(
+                len <= 32 && value.chars().all(|c| c.is_ascii_alphabetic()),
+                "32位以内字母，只能字母",
+            ),
+            KeywordRule::Symbol => check(
+                len <= 5 && value.chars().all(|c| c.is_ascii_punctuation()),
+                "5位以内符号，只能符号",
+            ),
+            KeywordRule::CharacterString => check(
+                len <= 32 && value.chars().all(|c| c.is_ascii_graphic()),
+                "32位以内数字、字母或符号",
+            ),
+            KeywordRule::Time | KeywordRule::Date => check(
+                value.chars().any(|c| c.is_ascii_digit())
+                    && value.chars().all(|c| c.is_ascii_digit() || "年月日时分秒:-/.~ ".contains(c)),
+                "时间格式错误，如15:01或2019年10月1日 15:01",
+            ),
+            KeywordRule::Amount => check(is_amount(value), "1个币种符号+10位以内纯数字，可带小数，结尾可带\"元\""),
+            KeywordRule::PhoneNumber => check(
+                len <= 17 && value.chars().all(|c| c.is_ascii_digit() || "+-() ".contains(c)),
+                "17位以内数字、符号",
+            ),
+            KeywordRule::CarNumber => check(is_car_number(value), "8位以内，第一位与最后一位可为汉字，其余为字母或数字"),
+            KeywordRule::Name => match value.is_ascii() {
+                true => check(len <= 20, "20个以内字母或符号"),
+                false => check(len <= 10, "10个以内汉字"),
+            },
+            KeywordRule::Phrase => check(len <= 5 && value.chars().all(is_chinese), "5个以内纯汉字"),
+            KeywordRule::Enum => Ok(()),
+        }
+    }
+
+    /// 按长度限制截断参数值，仅截断文本类参数，数字、时间、金额等截断后含义会改变，原样返回
+    pub fn truncate(&self, value: &str) -> String {
+        let max = match self {
+            KeywordRule::Thing => 20,
+            KeywordRule::Letter | KeywordRule::CharacterString => 32,
+            KeywordRule::Symbol | KeywordRule::Phrase => 5,
+            KeywordRule::Name if value.is_ascii() => 20,
+            KeywordRule::Name => 10,
+            _ => return value.to_string(),
+        };
+        value.chars().take(max).collect()
+    }
+}
+
+/// 订阅消息模板，声明模板id和关键词后用于生成经过校验的模板数据
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubscribeTemplate {
+    /// 模板id
+    pub template_id: String,
+    /// 关键词名称及参数类型
+    pub keywords: Vec<(String, KeywordRule)>,
+}
+
+impl SubscribeTemplate {
+    /// 声明模板，关键词名称需与模板内容一致，如["thing1", "time2"]
+    pub fn new<S: Into<String>>(template_id: S, keywords: &[&str]) -> RPayResult<Self> {
+        let keywords = keywords
+            .iter()
+            .map(|keyword| Ok((keyword.to_string(), KeywordRule::from_keyword(keyword)?)))
+            .collect::<RPayResult<Vec<_>>>()?;
+        Ok(SubscribeTemplate { template_id: template_id.into(), keywords })
+    }
+
+    /// 开始填充模板数据
+    pub fn data(&self) -> SubscribeData<'_> {
+        SubscribeData { template: self, values: HashMap::new(), truncate: false }
+    }
+}
+
+impl TryFrom<&PrivateTemplate> for SubscribeTemplate {
+    type Error = RPayError;
+
+    fn try_from(template: &PrivateTemplate) -> Result<Self, Self::Error> {
+        let keywords = template.keywords();
+        let keywords: Vec<&str> = keywords.iter().map(String::as_str).collect();
+        SubscribeTemplate::new(template.pri_tmpl_id.clone(), &keywords)
+    }
+}
+
+/// 订阅消息模板数据
+#[derive(Debug, Clone)]
+pub struct SubscribeData<'a> {
+    template: &'a SubscribeTemplate,
+    values: HashMap<String, String>,
+    truncate: bool,
+}
+
+impl SubscribeData<'_> {
+    /// 设置关键词的值
+    pub fn set<K: Into<String>, V: Into<String>>(&mut self, keyword: K, value: V) -> &mut Self {
+        self.values.insert(keyword.into(), value.into());
+        self
+    }
+
+    /// 超出长度限制时截断文本类参数，默认不截断
+    pub fn truncate(&mut self, truncate: bool) -> &mut Self {
+        self.truncate = truncate;
+        self
+    }
+
+    /// 校验并生成模板数据，模板的每个关键词都必须有值，且不能包含模板以外的关键词
+    pub fn build(&self) -> RPayResult<HashMap<String, DataItem>> {
+        if let Some(keyword) = self
+            .values
+            .keys()
+            .find(|keyword| !self.template.keywords.iter().any(|(name, _)| name == *keyword))
+        {
+            return Err(RPayError::ErrorWithMsg(format!("模板中不存在关键词: {}", keyword)));
+        }
+        let mut data = HashMap::new();
+        for (keyword, rule) in &self.template.keywords {
+            let value = self
+                .values
+                .get(keyword)
+                .ok_or_else(|| RPayError::ErrorWithMsg(format!("缺少关键词: {}", keyword)))?;
+            let value = match self.truncate {
+                true => rule.truncate(value),
+                false => value.clone(),
+            };
+            rule.validate(&value)
+                .map_err(|err| RPayError::ErrorWithMsg(format!("关键词{}的值[{}]不符合规则: {}", keyword, value, err)))?;
+            data.insert(keyword.clone(), DataItem { value });
+        }
+        Ok(data)
+    }
+}
+
+/// 校验任意模板数据，关键词类型根据名称解析
+pub fn validate_data(data: &HashMap<String, DataItem>) -> RPayResult<()> {
+    for (keyword, item) in data {
+        KeywordRule::from_keyword(keyword)?
+            .validate(&item.value)
+            .map_err(|err| RPayError::ErrorWithMsg(format!("关键词{}的值[{}]不符合规则: {}", keyword, item.value, err)))?;
+    }
+    Ok(())
+}
+
+impl MessageBuilder {
+    /// 设置模板id和经过校验的模板数据
+    pub fn subscribe_data(&mut self, data: &SubscribeData) -> RPayResult<&mut Self> {
+        let items = data.build()?;
+        Ok(self.template_id(data.template.template_id.clone()).data(items))
+    }
+}
+
+fn is_chinese(c: char) -> bool {
+    ('\u{4e00}'..='\u{9fff}').contains(&c)
+}
+
+fn is_decimal(value: &str) -> bool {
+    let mut parts = value.splitn(2, '.');
+    let integer = parts.next().unwrap_or_default();
+    let fraction = parts.next();
+    !integer.is_empty()
+        && integer.chars().all(|c| c.is_ascii_digit())
+        && fraction.is_none_or(|f| !f.is_empty() && f.chars().all(|c| c.is_ascii_digit()))
+}
+
+fn is_amount(value: &str) -> bool {
+    let value = value.strip_suffix('元').unwrap_or(value);
+    let value = value.strip_prefix(|c: char| !c.is_ascii_digit()).unwrap_or(value);
+    let integer = value.split('.').next().unwrap_or_default();
+    integer.len() <= 10 && is_decimal(value)
+}
+
+fn is_car_number(value: &str) -> bool {
+    let chars: Vec<char> = value.chars().collect();
+    if chars.is_empty() || chars.len() > 8 {
+        return false;
+    }
+    chars.iter().enumerate().all(|(i, c)| {
+        c.is_ascii_alphanumeric() || ((i == 0 || i == chars.len() - 1) && is_chinese(*c))
+    })
+}
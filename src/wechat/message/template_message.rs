@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayResult,
+};
+
+/// 公众号模板消息
+/// https://developers.weixin.qq.com/doc/offiaccount/Message_Management/Template_Message_Interface.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct TemplateMessage {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    #[serde(skip)]
+    pub access_token: String,
+    /// 接收者openid
+    #[builder(setter(into))]
+    pub touser: String,
+    /// 模板ID
+    #[builder(setter(into))]
+    pub template_id: String,
+    /// 模板跳转链接(海外帐号没有跳转能力)
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// 跳小程序所需数据，url和miniprogram都传时优先跳转小程序
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub miniprogram: Option<TemplateMiniProgram>,
+    /// 防重入id，对于同一个openid + client_msg_id，只发送一条消息，10分钟有效
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_msg_id: Option<String>,
+    /// 模板数据
+    #[builder(default, setter(into))]
+    pub data: HashMap<String, TemplateData>,
+}
+
+impl TemplateMessageBuilder {
+    /// 添加一项模板数据
+    pub fn item<K: Into<String>>(&mut self, key: K, data: TemplateData) -> &mut Self {
+        self.data.get_or_insert_with(HashMap::new).insert(key.into(), data);
+        self
+    }
+}
+
+/// 模板消息跳转的小程序
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateMiniProgram {
+    /// 所需跳转到的小程序appid(该小程序appid必须与发模板消息的公众号是绑定关联关系，暂不支持小游戏)
+    pub appid: String,
+    /// 所需跳转到小程序的具体页面路径，支持带参数,(示例index?foo=bar)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pagepath: Option<String>,
+}
+
+/// 模板数据项
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateData {
+    /// 模板内容
+    pub value: String,
+    /// 模板内容字体颜色，不填默认为黑色
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub color: Option<String>,
+}
+
+impl TemplateData {
+    pub fn new<S: Into<String>>(value: S) -> Self {
+        TemplateData { value: value.into(), color: None }
+    }
+
+    /// 设置字体颜色，如#173177
+    pub fn color<S: Into<String>>(mut self, color: S) -> Self {
+        self.color = Some(color.into());
+        self
+    }
+}
+
+impl TemplateMessage {
+    /// 发送模板消息
+    pub async fn send(&self) -> RPayResult<TemplateMessageResponse> {
+        self.send_request()?.send().await
+    }
+
+    /// 发送模板消息(同步)
+    #[cfg(feature = "blocking")]
+    pub fn send_blocking(&self) -> RPayResult<TemplateMessageResponse> {
+        self.send_request()?.send_blocking()
+    }
+
+    fn send_request(&self) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com/cgi-bin/message/template/send?access_token={}", self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(serde_json::to_string(self)?)
+            .build()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TemplateMessageResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 消息id
+    pub msgid: Option<i64>,
+}
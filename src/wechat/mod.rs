@@ -5,4 +5,8 @@ pub mod common;
 pub mod model;
 pub mod auth;
 pub mod template;
-pub mod message;
\ No newline at end of file
+pub mod message;
+pub mod wxa;
+pub mod security;
+pub mod media;
+pub mod menu;
\ No newline at end of file
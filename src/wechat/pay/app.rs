@@ -1,7 +1,7 @@
 use derive_builder::Builder;
 use serde::{ Deserialize, Serialize};
 
-use crate::{core::request::Request, model::{Amount, Detail, Payer, SceneInfo, SettleInfo, SignData}, RPayResult};
+use crate::{core::request::Request, ApiResponse, model::{Amount, Detail, Payer, SceneInfo, SettleInfo, SignData}, RPayResult};
 
 use super::config::WechatV3PayConfig;
 
@@ -62,11 +62,36 @@ pub struct AppPay {
 /// 创建请求
 impl AppPay {
      pub async fn pay(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<AppResponse> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request::<AppResponse>(wechat_sdk, crate::common::HttpMethod::POST, "/v3/pay/transactions/app", json_body).await
+    }
+
+    /// 下单并返回响应信封，可以获取Request-ID等响应头信息
+    pub async fn pay_with_response(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<ApiResponse<AppResponse>> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request_with_response::<AppResponse>(wechat_sdk,crate::common::HttpMethod::POST, "/v3/pay/transactions/app", json_body).await
+    }
+
+    /// 下单(同步)
+    #[cfg(feature = "blocking")]
+    pub fn pay_blocking(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<AppResponse> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request_blocking::<AppResponse>(wechat_sdk, crate::common::HttpMethod::POST, "/v3/pay/transactions/app", json_body)
+    }
+
+    /// 下单并返回响应信封(同步)
+    #[cfg(feature = "blocking")]
+    pub fn pay_with_response_blocking(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<ApiResponse<AppResponse>> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request_with_response_blocking::<AppResponse>(wechat_sdk, crate::common::HttpMethod::POST, "/v3/pay/transactions/app", json_body)
+    }
+
+    // 填充商户信息并生成请求体
+    fn request_body(&mut self, wechat_sdk: &WechatV3PayConfig) -> RPayResult<String> {
         self.app_id = wechat_sdk.app_id.clone();
         self.mch_id = wechat_sdk.mch_id.clone();
         self.notify_url = wechat_sdk.notify_url.clone().unwrap_or_default();
-        let json_body = serde_json::to_string(self).unwrap();
-        Request::build_pay_request::<AppResponse>(wechat_sdk,crate::common::HttpMethod::POST, "/v3/pay/transactions/app", json_body).await
+        Ok(serde_json::to_string(self)?)
     }
 }
 
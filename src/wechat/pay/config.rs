@@ -1,9 +1,25 @@
 
+use std::{
+    fs,
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
+
+use crate::{RPayError, RPayResult};
+
+use rsa::RsaPrivateKey;
+
+use super::{
+    certificate,
+    signer::{RsaSigner, Signer},
+};
+
 /// 微信支付client
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
-#[builder(pattern = "mutable")]
+#[builder(pattern = "mutable", build_fn(private, name = "build_config"))]
 pub struct WechatV3PayConfig {
     /// 微信id
     #[builder(setter(into))]
@@ -30,16 +46,250 @@ pub struct WechatV3PayConfig {
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub refund_notify_url: Option<String>,
-    /// API证书序列号
-    #[builder(setter(strip_option, into))]
+    /// API证书序列号，可以通过certificate_file/pkcs12_file从商户证书中读取
+    #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub serial_no: Option<String>,
     /// API商户证书秘钥
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
-    // 证书文件
+    /// 商户私钥文件(apiclient_key.pem)路径，未配置private_key时读取
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key_path: Option<String>,
+    /// 商户证书文件(apiclient_cert.pem)路径，未配置serial_no时从证书中读取序列号
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub certificate_path: Option<String>,
+    /// PKCS#12证书文件(apiclient_cert.p12)路径，未配置私钥时在构建时读取私钥和证书序列号
     #[builder(default, setter(strip_option, into))]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub pkcs12_path: Option<String>,
+    /// PKCS#12证书密码，默认为商户号
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pkcs12_password: Option<String>,
+    /// 签名器，未设置时在构建时使用private_key创建RSA签名器，私钥只解析一次
+    #[builder(default, setter(strip_option))]
+    #[serde(skip, default)]
+    pub signer: Option<Arc<dyn Signer>>,
+    // 未设置signer时由private_key解析的签名器，首次签名时解析，clone后共享
+    #[builder(setter(skip))]
+    #[serde(skip)]
+    parsed_signer: OnceLock<Arc<dyn Signer>>,
+}
+
+impl WechatV3PayConfigBuilder {
+    /// 构建配置，私钥在构建时解析为签名器，私钥格式错误或配置了私钥但缺少证书序列号时构建失败
+    pub fn build(&self) -> Result<WechatV3PayConfig, WechatV3PayConfigBuilderError> {
+        let mut config = self.build_config()?;
+        config
+            .load_credentials()
+            .map_err(|err| WechatV3PayConfigBuilderError::ValidationError(err.to_string()))?;
+        if config.requires_serial_no() {
+            return Err(WechatV3PayConfigBuilderError::UninitializedField("serial_no"));
+        }
+        Ok(config)
+    }
+
+    /// 读取商户私钥文件(apiclient_key.pem)
+    pub fn private_key_file(&mut self, path: impl AsRef<Path>) -> RPayResult<&mut Self> {
+        let pem = fs::read(path)?;
+        self.private_key_pem(pem)
+    }
+
+    /// 设置PEM格式的商户私钥，支持PKCS#8和PKCS#1，私钥格式错误时直接返回错误
+    pub fn private_key_pem(&mut self, pem: impl AsRef<[u8]>) -> RPayResult<&mut Self> {
+        let pem = String::from_utf8(pem.as_ref().to_vec())?;
+        let private_key = certificate::parse_private_key(&pem)?;
+        self.set_private_key(private_key)
+    }
+
+    /// 读取商户证书文件(apiclient_cert.pem)，并从证书中读取证书序列号
+    pub fn certificate_file(&mut self, path: impl AsRef<Path>) -> RPayResult<&mut Self> {
+        let pem = fs::read(path)?;
+        self.certificate_pem(pem)
+    }
+
+    /// 设置PEM格式的商户证书，并从证书中读取证书序列号
+    pub fn certificate_pem(&mut self, pem: impl AsRef<[u8]>) -> RPayResult<&mut Self> {
+        let serial_no = certificate::serial_no_from_pem(pem)?;
+        Ok(self.serial_no(serial_no))
+    }
+
+    /// 读取PKCS#12证书文件(apiclient_cert.p12)，设置商户私钥和证书序列号，密码为空时使用商户号
+    pub fn pkcs12_file(&mut self, path: impl AsRef<Path>, password: Option<&str>) -> RPayResult<&mut Self> {
+        let der = fs::read(path)?;
+        self.pkcs12_der(der, password)
+    }
+
+    /// 设置PKCS#12证书内容，设置商户私钥和证书序列号，密码为空时使用商户号
+    pub fn pkcs12_der(&mut self, der: impl AsRef<[u8]>, password: Option<&str>) -> RPayResult<&mut Self> {
+        let password = match (password, &self.mch_id) {
+            (Some(password), _) => password.to_string(),
+            (None, Some(mch_id)) => mch_id.clone(),
+            (None, None) => return Err(RPayError::ErrorWithMsg(String::from("请先设置商户号或提供PKCS#12证书密码"))),
+        };
+        let credential = certificate::load_pkcs12(der.as_ref(), &password)?;
+        self.serial_no(credential.serial_no);
+        self.set_private_key(credential.private_key)
+    }
+
+    // 保存PKCS#8格式私钥，同时创建签名器
+    fn set_private_key(&mut self, private_key: RsaPrivateKey) -> RPayResult<&mut Self> {
+        let pem = certificate::private_key_to_pem(&private_key)?;
+        self.private_key(pem);
+        Ok(self.signer(Arc::new(RsaSigner::new(private_key))))
+    }
+}
+
+impl WechatV3PayConfig {
+    /// 从环境变量加载配置，prefix为变量名前缀，如"WECHAT"对应WECHAT_APP_ID、WECHAT_MCH_ID等
+    ///
+    /// | 环境变量 | 说明 |
+    /// | --- | --- |
+    /// | {prefix}_APP_ID | 必填 微信id |
+    /// | {prefix}_SECRET | 必填 秘钥 |
+    /// | {prefix}_MCH_ID | 必填 商户编号 |
+    /// | {prefix}_KEY_V3 | APIv3密钥 |
+    /// | {prefix}_API_KEY | APIv2密钥 |
+    /// | {prefix}_NOTIFY_URL | 支付回调地址 |
+    /// | {prefix}_REFUND_NOTIFY_URL | 退款回调地址 |
+    /// | {prefix}_SERIAL_NO | 证书序列号，可以由证书文件读取 |
+    /// | {prefix}_PRIVATE_KEY | 商户私钥内容 |
+    /// | {prefix}_PRIVATE_KEY_PATH | 商户私钥文件 |
+    /// | {prefix}_CERTIFICATE_PATH | 商户证书文件 |
+    /// | {prefix}_PKCS12_PATH | PKCS#12证书文件 |
+    /// | {prefix}_PKCS12_PASSWORD | PKCS#12证书密码 |
+    pub fn from_env(prefix: &str) -> RPayResult<Self> {
+        let var = |name: &str| {
+            let key = if prefix.is_empty() { name.to_string() } else { format!("{}_{}", prefix, name) };
+            std::env::var(&key).ok().filter(|v| !v.is_empty()).ok_or(key)
+        };
+        let required = |name: &str| var(name).map_err(RPayError::MissingConfig);
+        let optional = |name: &str| var(name).ok();
+        let mut config = WechatV3PayConfig {
+            app_id: required("APP_ID")?,
+            secret: required("SECRET")?,
+            mch_id: required("MCH_ID")?,
+            api_key_v3: optional("KEY_V3"),
+            api_key: optional("API_KEY"),
+            notify_url: optional("NOTIFY_URL"),
+            refund_notify_url: optional("REFUND_NOTIFY_URL"),
+            serial_no: optional("SERIAL_NO"),
+            private_key: optional("PRIVATE_KEY"),
+            private_key_path: optional("PRIVATE_KEY_PATH"),
+            certificate_path: optional("CERTIFICATE_PATH"),
+            pkcs12_path: optional("PKCS12_PATH"),
+            pkcs12_password: optional("PKCS12_PASSWORD"),
+            signer: None,
+            parsed_signer: OnceLock::new(),
+        };
+        config.prepare(|field| match prefix.is_empty() {
+            true => field.to_uppercase(),
+            false => format!("{}_{}", prefix, field.to_uppercase()),
+        })?;
+        Ok(config)
+    }
+
+    /// 从JSON字符串加载配置，字段名与结构体字段一致
+    pub fn from_json_str(json: &str) -> RPayResult<Self> {
+        let mut config: WechatV3PayConfig = serde_json::from_str(json)?;
+        config.prepare(str::to_string)?;
+        Ok(config)
+    }
+
+    /// 从JSON文件加载配置
+    pub fn from_json_file(path: impl AsRef<Path>) -> RPayResult<Self> {
+        Self::from_json_str(&fs::read_to_string(path)?)
+    }
+
+    /// 从TOML字符串加载配置，字段名与结构体字段一致
+    pub fn from_toml_str(content: &str) -> RPayResult<Self> {
+        let mut config: WechatV3PayConfig = toml::from_str(content)?;
+        config.prepare(str::to_string)?;
+        Ok(config)
+    }
+
+    /// 从TOML文件加载配置
+    pub fn from_toml_file(path: impl AsRef<Path>) -> RPayResult<Self> {
+        Self::from_toml_str(&fs::read_to_string(path)?)
+    }
+
+    /// 根据文件扩展名(.json/.toml)加载配置
+    pub fn from_file(path: impl AsRef<Path>) -> RPayResult<Self> {
+        let path = path.as_ref();
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => Self::from_json_file(path),
+            Some("toml") => Self::from_toml_file(path),
+            _ => Err(RPayError::ErrorWithMsg(format!("不支持的配置文件格式: {}", path.display()))),
+        }
+    }
+
+    // 读取证书并校验必填的证书序列号，field_name用于生成缺少配置项时的名称
+    fn prepare(&mut self, field_name: impl Fn(&str) -> String) -> RPayResult<()> {
+        self.load_credentials()?;
+        if self.requires_serial_no() {
+            return Err(RPayError::MissingConfig(field_name("serial_no")));
+        }
+        Ok(())
+    }
+
+    // 只有配置了私钥(签名器)时才需要签名请求，此时必须有证书序列号
+    fn requires_serial_no(&self) -> bool {
+        self.signer.is_some() && self.serial_no.is_none()
+    }
+
+    /// 读取私钥文件、商户证书和PKCS#12证书，并初始化签名器
+    pub fn load_credentials(&mut self) -> RPayResult<()> {
+        if let (None, Some(path)) = (&self.private_key, &self.private_key_path) {
+            self.private_key = Some(fs::read_to_string(path)?);
+        }
+        if let (None, Some(path)) = (&self.serial_no, &self.certificate_path) {
+            self.serial_no = Some(certificate::serial_no_from_pem(fs::read(path)?)?);
+        }
+        self.load_pkcs12()?;
+        self.init_signer()
+    }
+
+    /// 获取签名器
+    pub fn signer(&self) -> RPayResult<Arc<dyn Signer>> {
+        match (&self.signer, &self.private_key) {
+            (Some(signer), _) => Ok(signer.clone()),
+            // 未通过builder或加载方法构建(如直接反序列化)时才会走到这里，私钥只解析一次并保存在配置中
+            (None, Some(private_key)) => match self.parsed_signer.get() {
+                Some(signer) => Ok(signer.clone()),
+                None => {
+                    let signer: Arc<dyn Signer> = Arc::new(RsaSigner::from_pem(private_key)?);
+                    Ok(self.parsed_signer.get_or_init(|| signer).clone())
+                }
+            },
+            (None, None) => Err(RPayError::ErrorWithMsg(String::from("未配置商户私钥或签名器"))),
+        }
+    }
+
+    /// 配置了pkcs12_path且未配置私钥时，从PKCS#12证书读取私钥和证书序列号
+    pub fn load_pkcs12(&mut self) -> RPayResult<()> {
+        let Some(path) = &self.pkcs12_path else {
+            return Ok(());
+        };
+        if self.private_key.is_some() || self.signer.is_some() {
+            return Ok(());
+        }
+        let password = self.pkcs12_password.clone().unwrap_or_else(|| self.mch_id.clone());
+        let credential = certificate::load_pkcs12(&fs::read(path)?, &password)?;
+        self.private_key = Some(certificate::private_key_to_pem(&credential.private_key)?);
+        self.signer = Some(Arc::new(RsaSigner::new(credential.private_key)));
+        self.serial_no.get_or_insert(credential.serial_no);
+        Ok(())
+    }
+
+    /// 使用private_key初始化签名器，已设置签名器时不做处理
+    pub fn init_signer(&mut self) -> RPayResult<()> {
+        if let (None, Some(private_key)) = (&self.signer, &self.private_key) {
+            self.signer = Some(Arc::new(RsaSigner::from_pem(private_key)?));
+        }
+        Ok(())
+    }
 }
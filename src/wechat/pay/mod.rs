@@ -6,4 +6,7 @@ pub mod native;
 pub mod pay_info;
 pub mod refunds;
 pub mod config;
-pub mod parse_encrypt;
\ No newline at end of file
+pub mod parse_encrypt;
+pub mod signer;
+pub mod certificate;
+pub mod registry;
@@ -24,20 +24,37 @@ impl ParseEncrypt {
     
     /// 解密支付回调参数
     pub async fn parse(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<WechatPayDecodeData> {
+        self.decrypt(wechat_sdk)
+    }
+
+    /// 解密支付回调参数(同步)
+    #[cfg(feature = "blocking")]
+    pub fn parse_blocking(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<WechatPayDecodeData> {
+        self.decrypt(wechat_sdk)
+    }
+
+    fn decrypt(&self, wechat_sdk: WechatV3PayConfig) -> RPayResult<WechatPayDecodeData> {
+        let plain_text = self.decrypt_bytes(&wechat_sdk.api_key_v3.unwrap_or_default())?;
+        Ok(serde_json::from_slice(&plain_text)?)
+    }
+
+    /// 使用APIv3密钥解密回调资源，返回明文
+    pub fn decrypt_bytes(&self, api_key_v3: &str) -> RPayResult<Vec<u8>> {
         if self.nonce.len() != 12 {
             return Err(RPayError::ErrorWithMsg(String::from("nonce长度必须为12")));
         }
-        let api_key_v3 = &wechat_sdk.api_key_v3.unwrap_or_default();
+        if api_key_v3.len() != 32 {
+            return Err(RPayError::ErrorWithMsg(String::from("APIv3密钥长度必须为32")));
+        }
         let ciphertext = utils::base64_decode(self.ciphertext.clone())?;
         let aes_key = api_key_v3.as_bytes();
         let mut cipher = Aes256Gcm::new(aes_key.into());
         let payload = Payload {
-            msg: &ciphertext.as_slice(),
-            aad: &self.associated_data.as_bytes(),
+            msg: ciphertext.as_slice(),
+            aad: self.associated_data.as_bytes(),
         };
-        let plain_text = cipher
+        cipher
             .decrypt(self.nonce.as_bytes().into(), payload)
-            .map_err(|e| RPayError::ErrorWithMsg(e.to_string()))?;
-        Ok(serde_json::from_slice(&plain_text)?)
+            .map_err(|e| RPayError::ErrorWithMsg(e.to_string()))
     }
-}
\ No newline at end of file
+}
@@ -3,7 +3,7 @@ use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use uuid::Uuid;
 
-use crate::{model::{AppResult, JsapiResult, PayType}, utils, RPayError, RPayResult};
+use crate::{model::{AppResult, JsapiResult, PayType}, RPayError, RPayResult};
 
 use super::config::WechatV3PayConfig;
 
@@ -30,8 +30,19 @@ pub struct PayInfo {
 impl PayInfo {
     /// 获取支付签名
     pub async fn signature(&self, wechat_sdk: WechatV3PayConfig) -> RPayResult<Value> {
+        self.sign(wechat_sdk)
+    }
+
+    /// 获取支付签名(同步)
+    #[cfg(feature = "blocking")]
+    pub fn signature_blocking(&self, wechat_sdk: WechatV3PayConfig) -> RPayResult<Value> {
+        self.sign(wechat_sdk)
+    }
+
+    fn sign(&self, wechat_sdk: WechatV3PayConfig) -> RPayResult<Value> {
         let timestamp = chrono::Local::now().timestamp();
         let nonce_str = Uuid::new_v4().to_string().replace("-", "").to_uppercase();
+        let signer = wechat_sdk.signer()?;
         match self.pay_type {
             PayType::H5 => Ok(Value::String(
                 self.h5_url.to_owned().unwrap_or_default(),
@@ -44,13 +55,10 @@ impl PayInfo {
                     nonce_str,
                     prepay_id: prepay_id.clone(),
                     package: format!("prepay_id={}", prepay_id),
-                    sign_type: "RSA".to_string(),
+                    sign_type: signer.algorithm().sign_type().to_string(),
                     pay_sign: String::default(),
                 };
-                result.pay_sign = utils::sha256_sign(
-                    wechat_sdk.private_key.clone().unwrap_or_default(),
-                    result.clone().get_sign_str(),
-                )?;
+                result.pay_sign = signer.sign(result.get_sign_str().as_bytes())?;
                 Ok(serde_json::to_value(result)?)
             }
             PayType::Native => Ok(Value::String(
@@ -66,10 +74,7 @@ impl PayInfo {
                     prepay_id: self.prepay_id.clone().unwrap_or_default(),
                     sign: "".to_string(),
                 };
-                result.sign = utils::sha256_sign(
-                    wechat_sdk.private_key.clone().unwrap_or_default(),
-                    result.clone().get_sign_str(),
-                )?;
+                result.sign = signer.sign(result.get_sign_str().as_bytes())?;
                 Ok(serde_json::to_value(result)?)
             }
             PayType::Micro => Err(RPayError::ErrorWithMsg(String::from("该类型暂不支持"))),
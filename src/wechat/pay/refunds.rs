@@ -2,7 +2,7 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    core::request::Request, RPayResult
+    core::request::Request, ApiResponse, RPayResult
 };
 use super::config::WechatV3PayConfig;
 
@@ -41,9 +41,34 @@ pub struct Refunds {
 /// 实现方法
 impl Refunds {
     pub async fn pay(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<RefundResponse> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request::<RefundResponse>(wechat_sdk, crate::common::HttpMethod::POST, "/v3/refund/domestic/refunds", json_body).await
+    }
+
+    /// 申请退款并返回响应信封，可以获取Request-ID等响应头信息
+    pub async fn pay_with_response(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<ApiResponse<RefundResponse>> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request_with_response::<RefundResponse>(wechat_sdk,crate::common::HttpMethod::POST, "/v3/refund/domestic/refunds", json_body).await
+    }
+
+    /// 申请退款(同步)
+    #[cfg(feature = "blocking")]
+    pub fn pay_blocking(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<RefundResponse> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request_blocking::<RefundResponse>(wechat_sdk, crate::common::HttpMethod::POST, "/v3/refund/domestic/refunds", json_body)
+    }
+
+    /// 申请退款并返回响应信封(同步)
+    #[cfg(feature = "blocking")]
+    pub fn pay_with_response_blocking(&mut self, wechat_sdk: WechatV3PayConfig) -> RPayResult<ApiResponse<RefundResponse>> {
+        let json_body = self.request_body(&wechat_sdk)?;
+        Request::build_pay_request_with_response_blocking::<RefundResponse>(wechat_sdk, crate::common::HttpMethod::POST, "/v3/refund/domestic/refunds", json_body)
+    }
+
+    // 填充退款回调地址并生成请求体
+    fn request_body(&mut self, wechat_sdk: &WechatV3PayConfig) -> RPayResult<String> {
         self.notify_url = wechat_sdk.refund_notify_url.clone();
-        let json_body = serde_json::to_string(self).unwrap();
-        Request::build_pay_request::<RefundResponse>(wechat_sdk,crate::common::HttpMethod::POST, "/v3/refund/domestic/refunds", json_body).await
+        Ok(serde_json::to_string(self)?)
     }
 }
 
@@ -0,0 +1,197 @@
+use std::{collections::HashMap, sync::Arc};
+
+use dashmap::{mapref::entry::Entry, DashMap};
+use serde::de::DeserializeOwned;
+
+use crate::{RPayError, RPayResult};
+
+use super::{certificate::PlatformCertificate, config::WechatV3PayConfig, parse_encrypt::ParseEncrypt};
+
+/// 已注册的商户，包含已解析签名器的配置和微信支付平台证书
+#[derive(Debug, Clone)]
+pub struct Merchant {
+    /// 商户配置
+    pub config: WechatV3PayConfig,
+    /// 微信支付平台证书，key为证书序列号
+    pub platform_certificates: HashMap<String, PlatformCertificate>,
+}
+
+impl Merchant {
+    /// 商户编号
+    pub fn mch_id(&self) -> &str {
+        &self.config.mch_id
+    }
+}
+
+/// 多商户注册表，按商户号(mch_id)保存配置，可按app_id查找商户
+///
+/// 可以在多个线程间clone共享，insert/reload替换配置后立即生效，无需重启。
+#[derive(Debug, Clone, Default)]
+pub struct MerchantRegistry {
+    merchants: Arc<DashMap<String, Arc<Merchant>>>,
+    // app_id -> mch_id
+    app_ids: Arc<DashMap<String, String>>,
+    // 平台证书序列号 -> mch_id
+    serial_nos: Arc<DashMap<String, String>>,
+}
+
+impl MerchantRegistry {
+    /// 创建空的注册表
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 注册或替换商户配置，配置中的私钥和证书在注册时读取，返回被替换的商户
+    ///
+    /// 替换时保留已添加的平台证书。
+    pub fn insert(&self, mut config: WechatV3PayConfig) -> RPayResult<Option<Arc<Merchant>>> {
+        config.load_credentials()?;
+        Ok(self.put(config))
+    }
+
+    // 保存已读取证书的配置，不会失败
+    fn put(&self, config: WechatV3PayConfig) -> Option<Arc<Merchant>> {
+        // 持有entry期间其他线程不能修改该商户，替换和保留平台证书是原子的
+        let (merchant, old) = match self.merchants.entry(config.mch_id.clone()) {
+            Entry::Occupied(mut entry) => {
+                let platform_certificates = entry.get().platform_certificates.clone();
+                let merchant = Arc::new(Merchant { config, platform_certificates });
+                let old = entry.insert(merchant.clone());
+                (merchant, Some(old))
+            }
+            Entry::Vacant(entry) => {
+                let merchant = Arc::new(Merchant { config, platform_certificates: HashMap::new() });
+                entry.insert(merchant.clone());
+                (merchant, None)
+            }
+        };
+        if let Some(old) = &old {
+            if old.config.app_id != merchant.config.app_id {
+                self.app_ids.remove_if(&old.config.app_id, |_, mch_id| mch_id == old.mch_id());
+            }
+        }
+        self.bind_app_id(&merchant.config.app_id, merchant.mch_id());
+        old
+    }
+
+    /// 移除商户，同时移除对应的app_id和平台证书索引
+    pub fn remove(&self, mch_id: &str) -> Option<Arc<Merchant>> {
+        let (_, merchant) = self.merchants.remove(mch_id)?;
+        self.app_ids.retain(|_, v| v != mch_id);
+        self.serial_nos.retain(|_, v| v != mch_id);
+        Some(merchant)
+    }
+
+    /// 使用新的配置列表替换全部商户，不在列表中的商户会被移除
+    ///
+    /// 先读取并校验全部配置的证书，任何一个配置错误时注册表保持不变；
+    /// 校验通过后逐个替换，替换过程中不会失败，但并发读取可能短暂看到新旧商户混合的状态。
+    pub fn reload(&self, configs: Vec<WechatV3PayConfig>) -> RPayResult<()> {
+        let configs = configs
+            .into_iter()
+            .map(|mut config| config.load_credentials().map(|_| config))
+            .collect::<RPayResult<Vec<_>>>()?;
+        let mch_ids: Vec<String> = configs.iter().map(|config| config.mch_id.clone()).collect();
+        for config in configs {
+            self.put(config);
+        }
+        let removed: Vec<String> = self
+            .merchants
+            .iter()
+            .filter(|entry| !mch_ids.contains(entry.key()))
+            .map(|entry| entry.key().clone())
+            .collect();
+        for mch_id in removed {
+            self.remove(&mch_id);
+        }
+        Ok(())
+    }
+
+    /// 绑定app_id到商户，一个商户可以绑定多个app_id
+    pub fn bind_app_id(&self, app_id: &str, mch_id: &str) {
+        self.app_ids.insert(app_id.to_string(), mch_id.to_string());
+    }
+
+    /// 按商户号查找商户
+    pub fn get_by_mch_id(&self, mch_id: &str) -> Option<Arc<Merchant>> {
+        self.merchants.get(mch_id).map(|merchant| merchant.clone())
+    }
+
+    /// 按app_id查找商户
+    pub fn get_by_app_id(&self, app_id: &str) -> Option<Arc<Merchant>> {
+        let mch_id = self.app_ids.get(app_id)?.clone();
+        self.get_by_mch_id(&mch_id)
+    }
+
+    /// 按微信支付平台证书序列号查找商户
+    pub fn get_by_serial_no(&self, serial_no: &str) -> Option<Arc<Merchant>> {
+        let mch_id = self.serial_nos.get(serial_no)?.clone();
+        self.get_by_mch_id(&mch_id)
+    }
+
+    /// 已注册的商户号
+    pub fn mch_ids(&self) -> Vec<String> {
+        self.merchants.iter().map(|entry| entry.key().clone()).collect()
+    }
+
+    /// 已注册的商户数量
+    pub fn len(&self) -> usize {
+        self.merchants.len()
+    }
+
+    /// 是否没有注册商户
+    pub fn is_empty(&self) -> bool {
+        self.merchants.is_empty()
+    }
+
+    /// 为商户添加PEM格式的微信支付平台证书，返回证书序列号
+    ///
+    /// 证书在添加时解析并校验有效期，证书错误时直接返回错误。
+    pub fn add_platform_certificate(&self, mch_id: &str, pem: impl AsRef<[u8]>) -> RPayResult<String> {
+        let certificate = PlatformCertificate::from_pem(pem)?;
+        let serial_no = certificate.serial_no.clone();
+        let mut merchant = self
+            .merchants
+            .get_mut(mch_id)
+            .ok_or_else(|| RPayError::ErrorWithMsg(format!("商户未注册: {}", mch_id)))?;
+        // 在持有写锁时修改，避免与insert/reload并发时丢失更新
+        Arc::make_mut(&mut merchant)
+            .platform_certificates
+            .insert(serial_no.clone(), certificate);
+        self.serial_nos.insert(serial_no.clone(), mch_id.to_string());
+        Ok(serial_no)
+    }
+
+    /// 将回调通知路由到对应商户并解密资源
+    ///
+    /// serial_no为回调请求头Wechatpay-Serial，匹配到平台证书时直接使用对应商户的APIv3密钥解密；
+    /// 否则依次尝试各商户的APIv3密钥。多个商户可能共用APIv3密钥，解密后按资源中的mchid查找商户。
+    pub fn route_notification<T: DeserializeOwned>(
+        &self,
+        serial_no: Option<&str>,
+        resource: &ParseEncrypt,
+    ) -> RPayResult<(Arc<Merchant>, T)> {
+        let candidates = match serial_no.and_then(|serial_no| self.get_by_serial_no(serial_no)) {
+            Some(merchant) => vec![merchant],
+            None => self.merchants.iter().map(|entry| entry.value().clone()).collect(),
+        };
+        for merchant in candidates {
+            let Some(api_key_v3) = merchant.config.api_key_v3.as_deref() else {
+                continue;
+            };
+            // 密钥不匹配时GCM校验失败，尝试下一个商户
+            let Ok(plain_text) = resource.decrypt_bytes(api_key_v3) else {
+                continue;
+            };
+            let value: serde_json::Value = serde_json::from_slice(&plain_text)?;
+            let merchant = match value.get("mchid").and_then(|mchid| mchid.as_str()) {
+                Some(mchid) => self
+                    .get_by_mch_id(mchid)
+                    .ok_or_else(|| RPayError::ErrorWithMsg(format!("回调商户未注册: {}", mchid)))?,
+                None => merchant,
+            };
+            return Ok((merchant, serde_json::from_value(value)?));
+        }
+        Err(RPayError::ErrorWithMsg(String::from("没有匹配回调通知的商户")))
+    }
+}
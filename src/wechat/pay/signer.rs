@@ -0,0 +1,179 @@
+use std::collections::BTreeMap;
+use std::fmt::{Debug, Display, Formatter};
+
+use hmac::{Hmac, Mac};
+use rsa::sha2::{Digest, Sha256};
+use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+
+use crate::{utils, RPayError, RPayResult};
+
+use super::certificate;
+
+/// 签名算法
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SignAlgorithm {
+    /// V3 SHA256 with RSA
+    RsaSha256,
+    /// V2 HMAC-SHA256
+    HmacSha256,
+    /// V2 MD5
+    Md5,
+    /// 国密 SM2 with SM3，需要由外部签名服务实现
+    Sm2,
+}
+
+impl SignAlgorithm {
+    /// 调起支付时的签名类型(signType)
+    pub fn sign_type(&self) -> &'static str {
+        match self {
+            SignAlgorithm::RsaSha256 => "RSA",
+            SignAlgorithm::HmacSha256 => "HMAC-SHA256",
+            SignAlgorithm::Md5 => "MD5",
+            SignAlgorithm::Sm2 => "SM2",
+        }
+    }
+
+    /// V3接口Authorization请求头的认证类型，V2算法不支持V3接口
+    pub fn auth_schema(&self) -> Option<&'static str> {
+        match self {
+            SignAlgorithm::RsaSha256 => Some("WECHATPAY2-SHA256-RSA2048"),
+            SignAlgorithm::Sm2 => Some("WECHATPAY2-SM2-WITH-SM3"),
+            SignAlgorithm::HmacSha256 | SignAlgorithm::Md5 => None,
+        }
+    }
+}
+
+impl Display for SignAlgorithm {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(fmt, "{}", self.sign_type())
+    }
+}
+
+/// 签名器
+///
+/// 私钥可以保存在外部签名服务或本地代理进程中，只需实现该trait并通过
+/// `WechatV3PayConfigBuilder::signer`注入，应用内存中不再需要持有私钥。
+pub trait Signer: Send + Sync + Debug {
+    /// 签名算法
+    fn algorithm(&self) -> SignAlgorithm;
+
+    /// 对待签名串签名，V3返回base64编码的签名值，V2返回大写的十六进制签名值
+    fn sign(&self, content: &[u8]) -> RPayResult<String>;
+}
+
+/// V3 SHA256 with RSA 签名器，私钥只在创建时解析一次
+#[derive(Clone)]
+pub struct RsaSigner {
+    private_key: RsaPrivateKey,
+}
+
+impl RsaSigner {
+    /// 使用已解析的私钥创建签名器
+    pub fn new(private_key: RsaPrivateKey) -> Self {
+        Self { private_key }
+    }
+
+    /// 从PEM格式的私钥(apiclient_key.pem)创建签名器，支持PKCS#8和PKCS#1
+    pub fn from_pem(pem: impl AsRef<str>) -> RPayResult<Self> {
+        certificate::parse_private_key(pem).map(Self::new)
+    }
+}
+
+impl Debug for RsaSigner {
+    // 不输出私钥内容
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("RsaSigner").finish_non_exhaustive()
+    }
+}
+
+impl Signer for RsaSigner {
+    fn algorithm(&self) -> SignAlgorithm {
+        SignAlgorithm::RsaSha256
+    }
+
+    fn sign(&self, content: &[u8]) -> RPayResult<String> {
+        let hashed = Sha256::new().chain_update(content).finalize();
+        let padding = Pkcs1v15Sign::new::<Sha256>();
+        match self.private_key.sign(padding, &hashed) {
+            Ok(sn) => Ok(utils::base64_encode(sn)),
+            Err(_) => Err(RPayError::ErrorWithMsg(String::from("签名失败"))),
+        }
+    }
+}
+
+/// V2 HMAC-SHA256 签名器，待签名串会拼接`&key=API密钥`
+#[derive(Clone)]
+pub struct HmacSha256Signer {
+    api_key: String,
+}
+
+impl HmacSha256Signer {
+    /// 使用V2 API密钥创建签名器
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self { api_key: api_key.into() }
+    }
+}
+
+impl Debug for HmacSha256Signer {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("HmacSha256Signer").finish_non_exhaustive()
+    }
+}
+
+impl Signer for HmacSha256Signer {
+    fn algorithm(&self) -> SignAlgorithm {
+        SignAlgorithm::HmacSha256
+    }
+
+    fn sign(&self, content: &[u8]) -> RPayResult<String> {
+        let mut mac = Hmac::<Sha256>::new_from_slice(self.api_key.as_bytes())
+            .map_err(|err| RPayError::ErrorWithMsg(format!("HMAC密钥异常:{}", err)))?;
+        mac.update(content);
+        mac.update(b"&key=");
+        mac.update(self.api_key.as_bytes());
+        Ok(hex::encode_upper(mac.finalize().into_bytes()))
+    }
+}
+
+/// V2 MD5 签名器，待签名串会拼接`&key=API密钥`
+#[derive(Clone)]
+pub struct Md5Signer {
+    api_key: String,
+}
+
+impl Md5Signer {
+    /// 使用V2 API密钥创建签名器
+    pub fn new(api_key: impl Into<String>) -> Self {
+        Self { api_key: api_key.into() }
+    }
+}
+
+impl Debug for Md5Signer {
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> std::fmt::Result {
+        fmt.debug_struct("Md5Signer").finish_non_exhaustive()
+    }
+}
+
+impl Signer for Md5Signer {
+    fn algorithm(&self) -> SignAlgorithm {
+        SignAlgorithm::Md5
+    }
+
+    fn sign(&self, content: &[u8]) -> RPayResult<String> {
+        let mut context = md5::Context::new();
+        context.consume(content);
+        context.consume(b"&key=");
+        context.consume(self.api_key.as_bytes());
+        Ok(hex::encode_upper(context.compute().0))
+    }
+}
+
+/// V2接口的待签名串：参数按名称字典序排列，忽略空值和sign字段，使用`k=v&k=v`拼接
+pub fn v2_sign_content(params: &BTreeMap<String, String>) -> String {
+    params
+        .iter()
+        .filter(|(k, v)| !v.is_empty() && k.as_str() != "sign")
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join("&")
+}
@@ -1,6 +1,13 @@
+use std::sync::Arc;
+
 use uuid::Uuid;
 
-use crate::model::{SignData, WechatPay};
+use crate::{model::{SignData, WechatPay}, RPayError, RPayResult};
+
+use super::{
+    config::{WechatV3PayConfig, WechatV3PayConfigBuilder},
+    signer::Signer,
+};
 
 pub trait WechatPayTrait {
     fn app_id(&self) -> String;
@@ -10,7 +17,11 @@ pub trait WechatPayTrait {
     fn v3_key(&self) -> String;
     fn notify_url(&self) -> String;
     fn domain(&self) -> String;
-    fn rsa_sign(&self, content: impl AsRef<str>) -> String;
+    /// 签名器，私钥应只解析一次并复用
+    fn signer(&self) -> RPayResult<Arc<dyn Signer>>;
+    fn rsa_sign(&self, content: impl AsRef<str>) -> RPayResult<String> {
+        self.signer()?.sign(content.as_ref().as_bytes())
+    }
     fn now_timestamp(&self) -> String {
         chrono::Local::now().timestamp().to_string()
     }
@@ -18,10 +29,11 @@ pub trait WechatPayTrait {
         Uuid::new_v4().to_string().replace("-", "").to_uppercase()
     }
 
-    fn mut_sign_data<S>(&self, prefix: S, prepay_id: S) -> SignData
+    fn mut_sign_data<S>(&self, prefix: S, prepay_id: S) -> RPayResult<SignData>
     where
         S: AsRef<str>,
     {
+        let signer = self.signer()?;
         let app_id = self.app_id();
         let now_time = self.now_timestamp();
         let nonce_str = self.nonce_str();
@@ -30,15 +42,15 @@ pub trait WechatPayTrait {
             prefix = prefix.as_ref(),
             prepay_id = prepay_id.as_ref()
         );
-        let signed_str = self.rsa_sign(format!("{app_id}\n{now_time}\n{nonce_str}\n{ext_str}\n"));
-        SignData {
+        let signed_str = signer.sign(format!("{app_id}\n{now_time}\n{nonce_str}\n{ext_str}\n").as_bytes())?;
+        Ok(SignData {
             app_id,
-            sign_type: "RSA".into(),
+            sign_type: signer.algorithm().sign_type().into(),
             package: ext_str,
             nonce_str,
             timestamp: now_time,
             pay_sign: signed_str,
-        }
+        })
     }
 }
 
@@ -63,15 +75,33 @@ impl WechatPay {
         }
     }
 
-    pub fn from_env() -> Self {
-        // let secret = std::env::var("WECHAT_SECRET").expect("WECHAT_SECRET not found");
-        let app_id = std::env::var("WECHAT_APP_ID").expect("WECHAT_APP_ID not found");
-        let mch_id = std::env::var("WECHAT_MCH_ID").expect("WECHAT_MCH_ID not found");
-        let private_key =
-            std::env::var("WECHAT_PRIVATE_KEY").expect("WECHAT_PRIVATE_KEY not found");
-        let serial_no = std::env::var("WECHAT_SERIAL_NO").expect("WECHAT_SERIAL_NO not found");
-        let v3_key = std::env::var("WECHAT_KEY_V3").expect("WECHAT_KEY_V3 not found");
-        let notify_url = std::env::var("WECHAT_NOTIFY_URL").expect("WECHAT_NOTIFY_URL not found");
-        Self::new(app_id, mch_id, private_key, serial_no, v3_key, notify_url)
+    /// 从环境变量WECHAT_APP_ID、WECHAT_MCH_ID、WECHAT_PRIVATE_KEY、WECHAT_SERIAL_NO、WECHAT_KEY_V3、WECHAT_NOTIFY_URL加载
+    pub fn from_env() -> RPayResult<Self> {
+        let var = |key: &str| std::env::var(key).map_err(|_| RPayError::MissingConfig(key.to_string()));
+        Ok(Self::new(
+            var("WECHAT_APP_ID")?,
+            var("WECHAT_MCH_ID")?,
+            var("WECHAT_PRIVATE_KEY")?,
+            var("WECHAT_SERIAL_NO")?,
+            var("WECHAT_KEY_V3")?,
+            var("WECHAT_NOTIFY_URL")?,
+        ))
     }
-}
\ No newline at end of file
+}
+
+/// 转换为支付接口使用的配置，私钥在转换时解析
+impl TryFrom<WechatPay> for WechatV3PayConfig {
+    type Error = RPayError;
+
+    fn try_from(value: WechatPay) -> Result<Self, Self::Error> {
+        Ok(WechatV3PayConfigBuilder::default()
+            .app_id(value.app_id)
+            .secret(String::new())
+            .mch_id(value.mch_id)
+            .private_key(value.private_key)
+            .serial_no(value.serial_no)
+            .api_key_v3(value.v3_key)
+            .notify_url(value.notify_url)
+            .build()?)
+    }
+}
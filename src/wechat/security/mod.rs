@@ -0,0 +1,2 @@
+pub mod sec_check;
+pub use sec_check::*;
\ No newline at end of file
@@ -0,0 +1,263 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    message::inbound::{int, one_or_many, opt_int, MessageHeader},
+    RPayResult,
+};
+
+/// 内容安全检测
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/sec-center/sec-check/msgSecCheck.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct SecurityCheck {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl SecurityCheck {
+    /// 文本内容安全识别
+    pub async fn msg_sec_check(&self, params: &MsgSecCheck) -> RPayResult<MsgSecCheckResponse> {
+        self.request("/wxa/msg_sec_check", params)?.send().await
+    }
+
+    /// 文本内容安全识别(同步)
+    #[cfg(feature = "blocking")]
+    pub fn msg_sec_check_blocking(&self, params: &MsgSecCheck) -> RPayResult<MsgSecCheckResponse> {
+        self.request("/wxa/msg_sec_check", params)?.send_blocking()
+    }
+
+    /// 音视频内容安全识别，检测结果通过消息推送[`MediaCheckEvent`]异步通知
+    pub async fn media_check_async(&self, params: &MediaCheckAsync) -> RPayResult<MediaCheckAsyncResponse> {
+        self.request("/wxa/media_check_async", params)?.send().await
+    }
+
+    /// 音视频内容安全识别(同步)
+    #[cfg(feature = "blocking")]
+    pub fn media_check_async_blocking(&self, params: &MediaCheckAsync) -> RPayResult<MediaCheckAsyncResponse> {
+        self.request("/wxa/media_check_async", params)?.send_blocking()
+    }
+
+    fn request(&self, path: &str, params: &impl Serialize) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(serde_json::to_string(params)?)
+            .build()?)
+    }
+}
+
+/// 检测场景
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CheckScene {
+    /// 资料
+    Profile = 1,
+    /// 评论
+    #[default]
+    Comment = 2,
+    /// 论坛
+    Forum = 3,
+    /// 社交日志
+    SocialLog = 4,
+}
+
+impl Serialize for CheckScene {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for CheckScene {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(CheckScene::Profile),
+            2 => Ok(CheckScene::Comment),
+            3 => Ok(CheckScene::Forum),
+            4 => Ok(CheckScene::SocialLog),
+            other => Err(serde::de::Error::custom(format!("未知的检测场景: {}", other))),
+        }
+    }
+}
+
+/// 多媒体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MediaType {
+    /// 音频
+    Audio = 1,
+    /// 图片
+    Image = 2,
+}
+
+impl Serialize for MediaType {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u8(*self as u8)
+    }
+}
+
+impl<'de> Deserialize<'de> for MediaType {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match u8::deserialize(deserializer)? {
+            1 => Ok(MediaType::Audio),
+            2 => Ok(MediaType::Image),
+            other => Err(serde::de::Error::custom(format!("未知的多媒体类型: {}", other))),
+        }
+    }
+}
+
+/// 建议
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Suggest {
+    /// 违规
+    #[serde(rename = "risky")]
+    Risky,
+    /// 通过
+    #[serde(rename = "pass")]
+    Pass,
+    /// 建议人工审核
+    #[serde(rename = "review")]
+    Review,
+}
+
+/// 命中标签枚举值说明
+pub fn label_name(label: i64) -> &'static str {
+    match label {
+        100 => "正常",
+        10001 => "广告",
+        20001 => "时政",
+        20002 => "色情",
+        20003 => "辱骂",
+        20006 => "违法犯罪",
+        20008 => "欺诈",
+        20012 => "低俗",
+        20013 => "版权",
+        21000 => "其他",
+        _ => "未知",
+    }
+}
+
+/// 文本内容安全识别参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct MsgSecCheck {
+    /// 需检测的文本内容，文本字数的上限为2500字，需使用UTF-8编码
+    #[builder(setter(into))]
+    pub content: String,
+    /// 接口版本号，固定为2
+    #[builder(setter(skip), default = "2")]
+    pub version: u8,
+    /// 场景枚举值
+    #[builder(default)]
+    pub scene: CheckScene,
+    /// 用户的openid，用户需在近两小时访问过小程序
+    #[builder(setter(into))]
+    pub openid: String,
+    /// 文本标题
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// 用户昵称
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nickname: Option<String>,
+    /// 个性签名，仅在资料类场景有效
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// 音视频内容安全识别参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct MediaCheckAsync {
+    /// 要检测的多媒体url
+    #[builder(setter(into))]
+    pub media_url: String,
+    /// 多媒体类型
+    pub media_type: MediaType,
+    /// 接口版本号，固定为2
+    #[builder(setter(skip), default = "2")]
+    pub version: u8,
+    /// 场景枚举值
+    #[builder(default)]
+    pub scene: CheckScene,
+    /// 用户的openid，用户需在近两小时访问过小程序
+    #[builder(setter(into))]
+    pub openid: String,
+}
+
+/// 综合结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckResult {
+    /// 建议
+    pub suggest: Suggest,
+    /// 命中标签枚举值，100正常
+    #[serde(deserialize_with = "int")]
+    pub label: i64,
+}
+
+/// 详细检测结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckDetail {
+    /// 策略类型
+    pub strategy: Option<String>,
+    /// 错误码，仅当该值为0时，该项结果有效
+    #[serde(default, deserialize_with = "opt_int")]
+    pub errcode: Option<i64>,
+    /// 建议
+    pub suggest: Option<Suggest>,
+    /// 命中标签枚举值
+    #[serde(default, deserialize_with = "opt_int")]
+    pub label: Option<i64>,
+    /// 命中的自定义关键词
+    pub keyword: Option<String>,
+    /// 0-100，代表置信度，越高代表越有可能属于当前返回的标签
+    #[serde(default, deserialize_with = "opt_int")]
+    pub prob: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MsgSecCheckResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 唯一请求标识，标记单次请求
+    pub trace_id: Option<String>,
+    /// 综合结果
+    pub result: Option<CheckResult>,
+    /// 详细检测结果
+    #[serde(default)]
+    pub detail: Vec<CheckDetail>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaCheckAsyncResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 唯一请求标识，标记单次请求，用于匹配异步推送结果
+    pub trace_id: Option<String>,
+}
+
+/// 音视频内容安全识别结果推送(Event为wxa_media_check)，消息推送中解析为[`InboundEvent::WxaMediaCheck`](crate::message::inbound::InboundEvent::WxaMediaCheck)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MediaCheckEvent {
+    #[serde(flatten)]
+    pub header: MessageHeader,
+    /// 事件类型，固定为wxa_media_check
+    #[serde(rename = "Event")]
+    pub event: String,
+    /// 小程序的appid
+    pub appid: String,
+    /// 任务id，与media_check_async返回的trace_id一致
+    pub trace_id: String,
+    /// 接口版本号
+    #[serde(deserialize_with = "int")]
+    pub version: i64,
+    /// 详细检测结果，XML格式中只有一项时为对象
+    #[serde(default, deserialize_with = "one_or_many")]
+    pub detail: Vec<CheckDetail>,
+    /// 综合结果
+    pub result: CheckResult,
+}
@@ -1,2 +1,3 @@
 pub mod template;
+pub mod official;
 pub use template::*;
\ No newline at end of file
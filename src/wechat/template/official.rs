@@ -0,0 +1,171 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayResult,
+};
+
+/// 公众号模板消息的模板管理
+/// https://developers.weixin.qq.com/doc/offiaccount/Message_Management/Template_Message_Interface.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct OfficialTemplates {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl OfficialTemplates {
+    /// 设置所属行业，每月可修改行业1次
+    pub async fn set_industry(&self, industry_id1: &str, industry_id2: &str) -> RPayResult<OfficialResponse> {
+        self.set_industry_request(industry_id1, industry_id2)?.send().await
+    }
+
+    /// 设置所属行业(同步)
+    #[cfg(feature = "blocking")]
+    pub fn set_industry_blocking(&self, industry_id1: &str, industry_id2: &str) -> RPayResult<OfficialResponse> {
+        self.set_industry_request(industry_id1, industry_id2)?.send_blocking()
+    }
+
+    /// 获取设置的行业信息
+    pub async fn get_industry(&self) -> RPayResult<IndustryResponse> {
+        self.get_request("/cgi-bin/template/get_industry")?.send().await
+    }
+
+    /// 获取设置的行业信息(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_industry_blocking(&self) -> RPayResult<IndustryResponse> {
+        self.get_request("/cgi-bin/template/get_industry")?.send_blocking()
+    }
+
+    /// 从模板库添加模板，返回模板ID
+    /// template_id_short为模板库中模板的编号，keyword_name_list为选用的类目模板的关键词
+    pub async fn add(&self, template_id_short: &str, keyword_name_list: &[&str]) -> RPayResult<AddTemplateResponse> {
+        self.add_request(template_id_short, keyword_name_list)?.send().await
+    }
+
+    /// 从模板库添加模板(同步)
+    #[cfg(feature = "blocking")]
+    pub fn add_blocking(&self, template_id_short: &str, keyword_name_list: &[&str]) -> RPayResult<AddTemplateResponse> {
+        self.add_request(template_id_short, keyword_name_list)?.send_blocking()
+    }
+
+    /// 获取已添加至帐号下所有模板列表
+    pub async fn get_all_private_template(&self) -> RPayResult<PrivateTemplateResponse> {
+        self.get_request("/cgi-bin/template/get_all_private_template")?.send().await
+    }
+
+    /// 获取已添加至帐号下所有模板列表(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_all_private_template_blocking(&self) -> RPayResult<PrivateTemplateResponse> {
+        self.get_request("/cgi-bin/template/get_all_private_template")?.send_blocking()
+    }
+
+    /// 删除模板
+    pub async fn delete(&self, template_id: &str) -> RPayResult<OfficialResponse> {
+        self.delete_request(template_id)?.send().await
+    }
+
+    /// 删除模板(同步)
+    #[cfg(feature = "blocking")]
+    pub fn delete_blocking(&self, template_id: &str) -> RPayResult<OfficialResponse> {
+        self.delete_request(template_id)?.send_blocking()
+    }
+
+    fn set_industry_request(&self, industry_id1: &str, industry_id2: &str) -> RPayResult<Request> {
+        let body = json!({
+            "industry_id1": industry_id1,
+            "industry_id2": industry_id2,
+        });
+        self.post_request("/cgi-bin/template/api_set_industry", body.to_string())
+    }
+
+    fn add_request(&self, template_id_short: &str, keyword_name_list: &[&str]) -> RPayResult<Request> {
+        let body = json!({
+            "template_id_short": template_id_short,
+            "keyword_name_list": keyword_name_list,
+        });
+        self.post_request("/cgi-bin/template/api_add_template", body.to_string())
+    }
+
+    fn delete_request(&self, template_id: &str) -> RPayResult<Request> {
+        let body = json!({ "template_id": template_id });
+        self.post_request("/cgi-bin/template/del_private_template", body.to_string())
+    }
+
+    fn get_request(&self, path: &str) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+
+    fn post_request(&self, path: &str, body: String) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(body)
+            .build()?)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OfficialResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+}
+
+/// 行业信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Industry {
+    /// 主行业
+    pub first_class: String,
+    /// 副行业
+    pub second_class: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IndustryResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 帐号设置的主营行业
+    pub primary_industry: Option<Industry>,
+    /// 帐号设置的副营行业
+    pub secondary_industry: Option<Industry>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddTemplateResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 模板ID
+    pub template_id: Option<String>,
+}
+
+/// 已添加的模板
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateTemplate {
+    /// 模板ID
+    pub template_id: String,
+    /// 模板标题
+    pub title: String,
+    /// 模板所属行业的一级行业
+    pub primary_industry: Option<String>,
+    /// 模板所属行业的二级行业
+    pub deputy_industry: Option<String>,
+    /// 模板内容
+    pub content: String,
+    /// 模板示例
+    pub example: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateTemplateResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 模板列表
+    #[serde(default)]
+    pub template_list: Vec<PrivateTemplate>,
+}
@@ -2,7 +2,7 @@ use derive_builder::Builder;
 use serde::{ Deserialize, Serialize};
 use serde_json::json;
 
-use crate::{core::request::RequestBuilder, RPayResult};
+use crate::{common::HttpMethod, core::request::{Request, RequestBuilder}, message::subscribe::KeywordRule, RPayError, RPayResult};
 
 
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
@@ -15,38 +15,134 @@ pub struct Templates {
 impl Templates {
     // https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/mp-message-management/subscribe-message/deleteMessageTemplate.html
     pub async fn delete(&mut self, template_id: String) -> RPayResult<Response> {
-        let body = json!({
-            "priTmplId": template_id
-        });
-        println!("body = {}", body);
-        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/deltemplate?access_token={}", self.access_token);
-        let resp = RequestBuilder::default()
-            .url(url)
-            .body(body.to_string())
-            .build()?.send::<Response>().await?;
+        let resp = self.delete_request(template_id)?.send::<Response>().await?;
         Ok(resp)
     }
 
+    /// 删除模板(同步)
+    #[cfg(feature = "blocking")]
+    pub fn delete_blocking(&mut self, template_id: String) -> RPayResult<Response> {
+        self.delete_request(template_id)?.send_blocking::<Response>()
+    }
+
     // 获取类目
     pub async fn get_category(&self) -> RPayResult<TemplatesResponse<Vec<CategoryResponse>>> {
-        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/getcategory?access_token={}", self.access_token);
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?.send().await?;
+        let resp = self.get_category_request()?.send().await?;
         Ok(resp)
     }
 
+    /// 获取类目(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_category_blocking(&self) -> RPayResult<TemplatesResponse<Vec<CategoryResponse>>> {
+        self.get_category_request()?.send_blocking()
+    }
+
     /// 获取关键词列表
     /// 属性	类型	必填	说明
     // access_token	string	是	接口调用凭证，该参数为 URL 参数，非 Body 参数。使用access_token或者authorizer_access_token
     // tid	string	是	模板标题 id，可通过接口获取
     pub async fn get_pub_template_keywords(&self, tid: String) -> RPayResult<TemplatesResponse<Vec<KeywordsResponse>>> {
-        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/getpubtemplatekeywords?access_token={}&tid={}", self.access_token, tid);
-        let resp = RequestBuilder::default()
-            .url(url)
-            .build()?.send().await?;
+        let resp = self.get_pub_template_keywords_request(tid)?.send().await?;
         Ok(resp)
     }
+
+    /// 获取关键词列表(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_pub_template_keywords_blocking(&self, tid: String) -> RPayResult<TemplatesResponse<Vec<KeywordsResponse>>> {
+        self.get_pub_template_keywords_request(tid)?.send_blocking()
+    }
+
+    /// 获取所属类目下的公共模板
+    /// ids为类目id，多个用逗号隔开；start用于分页，表示从start开始，从0开始计数；limit用于分页，表示拉取limit条记录，最大为30
+    pub async fn get_pub_template_titles(&self, params: &PubTemplateTitles) -> RPayResult<PubTemplateTitlesResponse> {
+        self.get_pub_template_titles_request(params)?.send().await
+    }
+
+    /// 获取所属类目下的公共模板(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_pub_template_titles_blocking(&self, params: &PubTemplateTitles) -> RPayResult<PubTemplateTitlesResponse> {
+        self.get_pub_template_titles_request(params)?.send_blocking()
+    }
+
+    /// 添加模板到个人模板库，返回添加至帐号下的模板id
+    /// kid_list为开发者自行组合好的模板关键词列表，关键词顺序可以自由搭配，最多支持5个，最少2个关键词组合
+    pub async fn add_template(&self, tid: &str, kid_list: &[i64], scene_desc: &str) -> RPayResult<AddTemplateResponse> {
+        self.add_template_request(tid, kid_list, scene_desc)?.send().await
+    }
+
+    /// 添加模板(同步)
+    #[cfg(feature = "blocking")]
+    pub fn add_template_blocking(&self, tid: &str, kid_list: &[i64], scene_desc: &str) -> RPayResult<AddTemplateResponse> {
+        self.add_template_request(tid, kid_list, scene_desc)?.send_blocking()
+    }
+
+    /// 获取个人模板列表
+    pub async fn get_template(&self) -> RPayResult<TemplatesResponse<Vec<PrivateTemplate>>> {
+        self.get_template_request()?.send().await
+    }
+
+    /// 获取个人模板列表(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_template_blocking(&self) -> RPayResult<TemplatesResponse<Vec<PrivateTemplate>>> {
+        self.get_template_request()?.send_blocking()
+    }
+
+    fn get_pub_template_titles_request(&self, params: &PubTemplateTitles) -> RPayResult<Request> {
+        let ids = params.ids.iter().map(|id| id.to_string()).collect::<Vec<_>>().join(",");
+        let mut query = vec![
+            ("access_token", self.access_token.clone()),
+            ("ids", ids),
+            ("start", params.start.to_string()),
+            ("limit", params.limit.to_string()),
+        ];
+        if let Some(keyword) = &params.keyword {
+            query.push(("keyword", keyword.clone()));
+        }
+        let url = reqwest::Url::parse_with_params("https://api.weixin.qq.com/wxaapi/newtmpl/getpubtemplatetitles", &query)
+            .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))?;
+        Ok(RequestBuilder::default().url(url.to_string()).build()?)
+    }
+
+    fn add_template_request(&self, tid: &str, kid_list: &[i64], scene_desc: &str) -> RPayResult<Request> {
+        let body = json!({
+            "tid": tid,
+            "kidList": kid_list,
+            "sceneDesc": scene_desc,
+        });
+        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/addtemplate?access_token={}", self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(body.to_string())
+            .build()?)
+    }
+
+    fn get_template_request(&self) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/gettemplate?access_token={}", self.access_token);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+
+    fn delete_request(&self, template_id: String) -> RPayResult<Request> {
+        let body = json!({
+            "priTmplId": template_id
+        });
+        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/deltemplate?access_token={}", self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(body.to_string())
+            .build()?)
+    }
+
+    fn get_category_request(&self) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/getcategory?access_token={}", self.access_token);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
+
+    fn get_pub_template_keywords_request(&self, tid: String) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com/wxaapi/newtmpl/getpubtemplatekeywords?access_token={}&tid={}", self.access_token, tid);
+        Ok(RequestBuilder::default().url(url).build()?)
+    }
 }
 
 // 获取类目返回数据
@@ -62,6 +158,13 @@ pub struct  KeywordsResponse {
     pub rule: String
 }
 
+impl KeywordsResponse {
+    /// 参数类型
+    pub fn keyword_rule(&self) -> RPayResult<KeywordRule> {
+        self.rule.parse()
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Builder)]
 pub struct Response {
     pub errmsg: String,
@@ -85,4 +188,100 @@ pub struct TemplatesResponse<T> {
     pub errcode: i64,
     // 返回数据
     pub data: Option<T>
-}
\ No newline at end of file
+}
+
+/// 获取公共模板标题参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct PubTemplateTitles {
+    /// 类目id，可通过get_category获取
+    #[builder(setter(into))]
+    pub ids: Vec<i64>,
+    /// 分页起始位置，从0开始计数
+    #[builder(default)]
+    pub start: u32,
+    /// 拉取条数，最大为30
+    #[builder(default = "30")]
+    pub limit: u32,
+    /// 搜索关键词
+    #[builder(default, setter(strip_option, into))]
+    pub keyword: Option<String>,
+}
+
+/// 公共模板标题
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PubTemplateTitle {
+    /// 模版标题id
+    pub tid: i64,
+    /// 模版标题
+    pub title: String,
+    /// 模版类型，2为一次性订阅，3为长期订阅
+    #[serde(rename = "type")]
+    pub template_type: i64,
+    /// 模版所属类目id
+    #[serde(rename = "categoryId")]
+    pub category_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PubTemplateTitlesResponse {
+    pub errcode: i64,
+    pub errmsg: String,
+    /// 模版标题列表总数
+    pub count: Option<i64>,
+    /// 模板标题列表
+    #[serde(default)]
+    pub data: Vec<PubTemplateTitle>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AddTemplateResponse {
+    pub errcode: i64,
+    pub errmsg: String,
+    /// 添加至帐号下的模板id，发送小程序订阅消息时所需
+    #[serde(rename = "priTmplId")]
+    pub pri_tmpl_id: Option<String>,
+}
+
+/// 个人模板
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivateTemplate {
+    /// 添加至帐号下的模板id
+    #[serde(rename = "priTmplId")]
+    pub pri_tmpl_id: String,
+    /// 模版标题
+    pub title: String,
+    /// 模版内容，如"{{thing1.DATA}}\n{{time2.DATA}}"
+    pub content: String,
+    /// 模板内容示例
+    pub example: String,
+    /// 模版类型，2为一次性订阅，3为长期订阅
+    #[serde(rename = "type")]
+    pub template_type: i64,
+}
+
+impl PrivateTemplate {
+    /// 按模板内容中的顺序返回关键词名称，如["thing1", "time2"]
+    pub fn keywords(&self) -> Vec<String> {
+        parse_keywords(&self.content)
+    }
+}
+
+/// 解析模板内容中的关键词名称，"{{thing1.DATA}}"解析为"thing1"
+pub fn parse_keywords(content: &str) -> Vec<String> {
+    let mut keywords = Vec::new();
+    let mut rest = content;
+    while let Some(start) = rest.find("{{") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("}}") else {
+            break;
+        };
+        let name = rest[..end].trim();
+        let name = name.strip_suffix(".DATA").unwrap_or(name);
+        if !name.is_empty() {
+            keywords.push(name.to_string());
+        }
+        rest = &rest[end + 2..];
+    }
+    keywords
+}
@@ -1,11 +1,10 @@
 use base64::engine::general_purpose;
 use base64::{DecodeError, Engine};
+use sha1::{Digest, Sha1};
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 use crate::{utils, RPayError, RPayResult};
-use rsa::pkcs8::DecodePrivateKey;
-use rsa::sha2::Digest;
-use rsa::{Pkcs1v15Sign, RsaPrivateKey};
+use crate::pay::signer::{RsaSigner, Signer};
 
 // 获取字符串
 pub fn get_nonce_str() -> String {
@@ -23,6 +22,11 @@ pub fn get_timestamp() -> RPayResult<i64> {
     }
 }
 
+// 获取时间戳，单位：秒
+pub fn get_timestamp_secs() -> i64 {
+    chrono::Local::now().timestamp()
+}
+
 // 随机订单号
 pub fn random_trade_no() -> String {
     Uuid::new_v4().to_simple().to_string()
@@ -44,22 +48,18 @@ where
     general_purpose::STANDARD.decode(content.as_ref())
 }
 
+/// SHA1摘要，小写十六进制
+pub fn sha1_hex<S>(content: S) -> String
+where
+    S: AsRef<[u8]>,
+{
+    hex::encode(Sha1::digest(content.as_ref()))
+}
+
 /// 加密信息
 pub fn sha256_sign(private_key: String, content: String) -> Result<String, RPayError> {
-    println!("sha256_sign => private_key, {}, content: {}", private_key, content);
-    match RsaPrivateKey::from_pkcs8_pem(&private_key) {
-        Ok(psk) => {
-            let hasher = rsa::sha2::Sha256::new()
-                .chain_update(content)
-                .finalize();
-            let padding = Pkcs1v15Sign::new::<rsa::sha2::Sha256>();
-            match  psk.sign(padding, &hasher) {
-                Ok(sn) => Ok(utils::base64_encode(sn)),
-                Err(_) => Err(RPayError::ErrorWithMsg(String::from("签名失败"))),
-            }            
-        }
-        Err(err) => Err(RPayError::ErrorWithMsg(format!("私钥解析失败:{:?}", err))),
-    }
+    // 每次调用都会解析私钥，频繁签名请使用 pay::signer::RsaSigner
+    RsaSigner::from_pem(private_key)?.sign(content.as_bytes())
 }
 
  /// 创建签名信息
@@ -0,0 +1,249 @@
+use derive_builder::Builder;
+use serde::{ser::SerializeMap, Deserialize, Serialize, Serializer};
+use serde_json::json;
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayResult,
+};
+
+use super::wxacode::EnvVersion;
+
+/// 小程序链接，用于短信、邮件、网页等场景打开小程序
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/url-scheme/generateScheme.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct WxaLink {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl WxaLink {
+    /// 获取加密URL Scheme
+    pub async fn generate_scheme(&self, params: &GenerateScheme) -> RPayResult<SchemeResponse> {
+        self.request("/wxa/generatescheme", params)?.send().await
+    }
+
+    /// 获取加密URL Scheme(同步)
+    #[cfg(feature = "blocking")]
+    pub fn generate_scheme_blocking(&self, params: &GenerateScheme) -> RPayResult<SchemeResponse> {
+        self.request("/wxa/generatescheme", params)?.send_blocking()
+    }
+
+    /// 查询加密URL Scheme
+    pub async fn query_scheme(&self, scheme: &str) -> RPayResult<QuerySchemeResponse> {
+        self.request("/wxa/queryscheme", &json!({ "scheme": scheme }))?.send().await
+    }
+
+    /// 查询加密URL Scheme(同步)
+    #[cfg(feature = "blocking")]
+    pub fn query_scheme_blocking(&self, scheme: &str) -> RPayResult<QuerySchemeResponse> {
+        self.request("/wxa/queryscheme", &json!({ "scheme": scheme }))?.send_blocking()
+    }
+
+    /// 获取URL Link
+    pub async fn generate_url_link(&self, params: &GenerateUrlLink) -> RPayResult<UrlLinkResponse> {
+        self.request("/wxa/generate_urllink", params)?.send().await
+    }
+
+    /// 获取URL Link(同步)
+    #[cfg(feature = "blocking")]
+    pub fn generate_url_link_blocking(&self, params: &GenerateUrlLink) -> RPayResult<UrlLinkResponse> {
+        self.request("/wxa/generate_urllink", params)?.send_blocking()
+    }
+
+    /// 查询URL Link
+    pub async fn query_url_link(&self, url_link: &str) -> RPayResult<QueryUrlLinkResponse> {
+        self.request("/wxa/query_urllink", &json!({ "url_link": url_link }))?.send().await
+    }
+
+    /// 查询URL Link(同步)
+    #[cfg(feature = "blocking")]
+    pub fn query_url_link_blocking(&self, url_link: &str) -> RPayResult<QueryUrlLinkResponse> {
+        self.request("/wxa/query_urllink", &json!({ "url_link": url_link }))?.send_blocking()
+    }
+
+    /// 获取Short Link
+    pub async fn generate_short_link(&self, params: &GenerateShortLink) -> RPayResult<ShortLinkResponse> {
+        self.request("/wxa/genwxashortlink", params)?.send().await
+    }
+
+    /// 获取Short Link(同步)
+    #[cfg(feature = "blocking")]
+    pub fn generate_short_link_blocking(&self, params: &GenerateShortLink) -> RPayResult<ShortLinkResponse> {
+        self.request("/wxa/genwxashortlink", params)?.send_blocking()
+    }
+
+    fn request(&self, path: &str, params: &impl Serialize) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(serde_json::to_string(params)?)
+            .build()?)
+    }
+}
+
+/// 链接失效方式，最长有效期为30天
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkExpire {
+    /// 到期失效的时间戳，单位：秒
+    At(i64),
+    /// 失效间隔天数，最长30天
+    Interval(u32),
+}
+
+impl Serialize for LinkExpire {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(Some(2))?;
+        match self {
+            LinkExpire::At(expire_time) => {
+                map.serialize_entry("expire_type", &0)?;
+                map.serialize_entry("expire_time", expire_time)?;
+            }
+            LinkExpire::Interval(expire_interval) => {
+                map.serialize_entry("expire_type", &1)?;
+                map.serialize_entry("expire_interval", expire_interval)?;
+            }
+        }
+        map.end()
+    }
+}
+
+/// 跳转到的目标小程序信息
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct JumpWxa {
+    /// 小程序页面路径，必须是已经发布的小程序存在的页面，不可携带query，为空时跳转小程序主页
+    #[builder(default, setter(into))]
+    pub path: String,
+    /// 进入小程序时的query，最大1024个字符
+    #[builder(default, setter(into))]
+    pub query: String,
+    /// 要打开的小程序版本，默认为正式版
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_version: Option<EnvVersion>,
+}
+
+/// 获取加密URL Scheme参数
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct GenerateScheme {
+    /// 跳转到的目标小程序信息
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub jump_wxa: Option<JumpWxa>,
+    /// 失效方式，默认30天后失效
+    #[builder(default, setter(strip_option))]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub expire: Option<LinkExpire>,
+}
+
+/// 获取URL Link参数
+#[derive(Debug, Clone, Serialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct GenerateUrlLink {
+    /// 小程序页面路径，不可携带query，为空时跳转小程序主页
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    /// 进入小程序时的query，最大1024个字符
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// 要打开的小程序版本，默认为正式版
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_version: Option<EnvVersion>,
+    /// 失效方式，默认30天后失效
+    #[builder(default, setter(strip_option))]
+    #[serde(flatten, skip_serializing_if = "Option::is_none")]
+    pub expire: Option<LinkExpire>,
+}
+
+/// 获取Short Link参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct GenerateShortLink {
+    /// 小程序页面路径，可携带query，最大1024个字符
+    #[builder(setter(into))]
+    pub page_url: String,
+    /// 页面标题，不能包含违法信息，超过20字符会用...截断代替
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_title: Option<String>,
+    /// 是否永久有效，默认false为临时链接，有效期30天
+    #[builder(default)]
+    pub is_permanent: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SchemeResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 生成的小程序scheme码
+    pub openlink: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UrlLinkResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 生成的小程序URL Link
+    pub url_link: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShortLinkResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// 生成的小程序Short Link
+    pub link: Option<String>,
+}
+
+/// 链接信息
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LinkInfo {
+    /// 小程序appid
+    pub appid: Option<String>,
+    /// 小程序页面路径
+    pub path: Option<String>,
+    /// 小程序页面query
+    pub query: Option<String>,
+    /// 创建时间，为Unix时间戳
+    pub create_time: Option<i64>,
+    /// 到期失效时间，为Unix时间戳，0表示永久生效
+    pub expire_time: Option<i64>,
+    /// 要打开的小程序版本
+    pub env_version: Option<EnvVersion>,
+}
+
+/// 访问配额
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuotaInfo {
+    /// 剩余访问次数
+    pub remain_visit_quota: Option<i64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuerySchemeResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// scheme配置
+    pub scheme_info: Option<LinkInfo>,
+    /// 访问配额
+    pub quota_info: Option<QuotaInfo>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QueryUrlLinkResponse {
+    pub errcode: Option<i64>,
+    pub errmsg: Option<String>,
+    /// url_link配置
+    pub url_link_info: Option<LinkInfo>,
+    /// 访问配额
+    pub quota_info: Option<QuotaInfo>,
+}
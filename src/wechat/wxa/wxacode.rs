@@ -0,0 +1,165 @@
+use bytes::Bytes;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    common::HttpMethod,
+    core::request::{Request, RequestBuilder},
+    RPayResult,
+};
+
+/// 小程序码，成功时返回图片二进制内容，失败时返回接口异常
+/// https://developers.weixin.qq.com/miniprogram/dev/OpenApiDoc/qrcode-link/qr-code/getQRCode.html
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct WxaCode {
+    /// 接口调用凭证
+    #[builder(setter(into))]
+    pub access_token: String,
+}
+
+impl WxaCode {
+    /// 获取小程序码，适用于需要的码数量较少的业务场景，总共生成的码数量限制为100,000
+    pub async fn get(&self, params: &GetWxaCode) -> RPayResult<Bytes> {
+        Ok(self.request("/wxa/getwxacode", params)?.send_bytes().await?.into_body())
+    }
+
+    /// 获取小程序码(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_blocking(&self, params: &GetWxaCode) -> RPayResult<Bytes> {
+        Ok(self.request("/wxa/getwxacode", params)?.send_bytes_blocking()?.into_body())
+    }
+
+    /// 获取不限制的小程序码，适用于需要的码数量极多的业务场景，通过scene区分
+    pub async fn get_unlimited(&self, params: &GetUnlimitedWxaCode) -> RPayResult<Bytes> {
+        Ok(self.request("/wxa/getwxacodeunlimit", params)?.send_bytes().await?.into_body())
+    }
+
+    /// 获取不限制的小程序码(同步)
+    #[cfg(feature = "blocking")]
+    pub fn get_unlimited_blocking(&self, params: &GetUnlimitedWxaCode) -> RPayResult<Bytes> {
+        Ok(self.request("/wxa/getwxacodeunlimit", params)?.send_bytes_blocking()?.into_body())
+    }
+
+    /// 获取小程序二维码，适用于需要的码数量较少的业务场景，总共生成的码数量限制为100,000
+    pub async fn create_qrcode(&self, params: &CreateQrCode) -> RPayResult<Bytes> {
+        Ok(self.request("/cgi-bin/wxaapp/createwxaqrcode", params)?.send_bytes().await?.into_body())
+    }
+
+    /// 获取小程序二维码(同步)
+    #[cfg(feature = "blocking")]
+    pub fn create_qrcode_blocking(&self, params: &CreateQrCode) -> RPayResult<Bytes> {
+        Ok(self.request("/cgi-bin/wxaapp/createwxaqrcode", params)?.send_bytes_blocking()?.into_body())
+    }
+
+    fn request(&self, path: &str, params: &impl Serialize) -> RPayResult<Request> {
+        let url = format!("https://api.weixin.qq.com{}?access_token={}", path, self.access_token);
+        Ok(RequestBuilder::default()
+            .url(url)
+            .method(HttpMethod::POST)
+            .body(serde_json::to_string(params)?)
+            .build()?)
+    }
+}
+
+/// 要打开的小程序版本
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum EnvVersion {
+    /// 正式版
+    #[serde(rename = "release")]
+    #[default]
+    Release,
+    /// 体验版
+    #[serde(rename = "trial")]
+    Trial,
+    /// 开发版
+    #[serde(rename = "develop")]
+    Develop,
+}
+
+/// 线条颜色，auto_color为false时生效
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LineColor {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+}
+
+/// 获取小程序码参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct GetWxaCode {
+    /// 扫码进入的小程序页面路径，最大长度1024个字符，可以携带query参数
+    #[builder(setter(into))]
+    pub path: String,
+    /// 二维码的宽度，单位px，最小280px，最大1280px，默认430
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// 要打开的小程序版本，默认为正式版
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_version: Option<EnvVersion>,
+    /// 自动配置线条颜色，默认false
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_color: Option<bool>,
+    /// 线条颜色
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_color: Option<LineColor>,
+    /// 是否需要透明底色，默认false
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_hyaline: Option<bool>,
+}
+
+/// 获取不限制的小程序码参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct GetUnlimitedWxaCode {
+    /// 场景值，最大32个可见字符，只支持数字，大小写英文以及部分特殊字符：!#$&'()*+,/:;=?@-._~
+    #[builder(setter(into))]
+    pub scene: String,
+    /// 页面路径，根路径前不要填加/，不能携带参数，默认是主页
+    #[builder(default, setter(strip_option, into))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page: Option<String>,
+    /// 是否检查page是否存在，默认true，为false时允许小程序未发布或者page不存在
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub check_path: Option<bool>,
+    /// 要打开的小程序版本，默认为正式版
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub env_version: Option<EnvVersion>,
+    /// 二维码的宽度，单位px，最小280px，最大1280px，默认430
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+    /// 自动配置线条颜色，默认false
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub auto_color: Option<bool>,
+    /// 线条颜色
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_color: Option<LineColor>,
+    /// 是否需要透明底色，默认false
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub is_hyaline: Option<bool>,
+}
+
+/// 获取小程序二维码参数
+#[derive(Debug, Clone, Serialize, Deserialize, Builder)]
+#[builder(pattern = "mutable")]
+pub struct CreateQrCode {
+    /// 扫码进入的小程序页面路径，最大长度128个字符，可以携带query参数
+    #[builder(setter(into))]
+    pub path: String,
+    /// 二维码的宽度，单位px，最小280px，最大1280px，默认430
+    #[builder(default, setter(strip_option))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub width: Option<u32>,
+}
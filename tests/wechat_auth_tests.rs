@@ -3,30 +3,28 @@ mod tests {
 
     use dotenvy::dotenv;
     use rpay::{
-        pay::config::{WechatV3PayConfig, WechatV3PayConfigBuilder},
+        auth::{phone_number::PhoneNumberBuilder, token_manager::AccessTokenManager},
+        pay::config::WechatV3PayConfig,
         RPayResult,
     };
     
     // sdk公共参数
     fn get_sdk() -> RPayResult<WechatV3PayConfig> {
         dotenv().ok();
-        let app_id = std::env::var("WECHAT_APP_ID").expect("WECHAT_APP_ID not found");
-        let mch_id = std::env::var("WECHAT_MCH_ID").expect("WECHAT_MCH_ID not found");
-        let secret = std::env::var("WECHAT_SECRET").expect("WECHAT_SECRET not found");
-        let private_key =
-            std::env::var("WECHAT_PRIVATE_KEY").expect("WECHAT_PRIVATE_KEY not found");
-        let serial_no = std::env::var("WECHAT_SERIAL_NO").expect("WECHAT_SERIAL_NO not found");
-        let v3_key = std::env::var("WECHAT_KEY_V3").expect("WECHAT_KEY_V3 not found");
-        let notify_url = std::env::var("WECHAT_NOTIFY_URL").expect("WECHAT_NOTIFY_URL not found");
-        let sdk = WechatV3PayConfigBuilder::default()
-            .app_id(app_id)
-            .secret(secret)
-            .api_key_v3(v3_key)
-            .serial_no(serial_no.to_string())
-            .mch_id(mch_id)
-            .private_key(private_key)
-            .notify_url(notify_url)
-            .build()?;
-        Ok(sdk)
+        WechatV3PayConfig::from_env("WECHAT")
+    }
+
+    /// 使用手机号快速验证组件返回的code获取手机号，code只能使用一次
+    #[tokio::test]
+    async fn test_get_phone_number() -> RPayResult<()> {
+        let sdk = get_sdk()?;
+        let tokens = AccessTokenManager::new(sdk.app_id, sdk.secret)?;
+        let phone_info = PhoneNumberBuilder::default()
+            .code(std::env::var("WECHAT_PHONE_CODE").unwrap_or_default())
+            .build()?
+            .request_with(&tokens)
+            .await?;
+        println!("phone_info => {:?}", phone_info);
+        Ok(())
     }
 }
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod tests {
+
+    use rpay::{
+        pay::config::{WechatV3PayConfig, WechatV3PayConfigBuilder},
+        RPayError, RPayResult,
+    };
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    /// 测试从TOML加载配置，私钥和证书使用文件引用
+    #[test]
+    fn test_from_toml_str() -> RPayResult<()> {
+        let content = format!(
+            r#"
+            app_id = "wx_app_id"
+            secret = "secret"
+            mch_id = "1900000001"
+            api_key_v3 = "api_key_v3"
+            private_key_path = "{}"
+            certificate_path = "{}"
+            "#,
+            fixture("apiclient_key.pem"),
+            fixture("apiclient_cert.pem")
+        );
+        let config = WechatV3PayConfig::from_toml_str(&content)?;
+        assert_eq!(config.serial_no.as_deref(), Some("4B3C1E2A6F3C9D1E8A7B6C5D4E3F2A1B0C9D8E7F"));
+        assert!(config.signer.is_some());
+        Ok(())
+    }
+
+    /// 测试从JSON加载配置，缺少证书序列号时返回字段名
+    #[test]
+    fn test_from_json_str_missing_serial_no() {
+        let content = format!(
+            r#"{{"app_id":"wx_app_id","secret":"secret","mch_id":"1900000001","private_key_path":"{}"}}"#,
+            fixture("apiclient_key.pem")
+        );
+        match WechatV3PayConfig::from_json_str(&content) {
+            Err(RPayError::MissingConfig(field)) => assert_eq!(field, "serial_no"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    /// 测试未配置私钥时不需要证书序列号，如只使用公众号或小程序接口
+    #[test]
+    fn test_config_without_private_key() -> RPayResult<()> {
+        let config = WechatV3PayConfigBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .mch_id("1900000001")
+            .build()?;
+        assert!(config.serial_no.is_none());
+        assert!(config.signer().is_err());
+
+        let config = WechatV3PayConfig::from_json_str(r#"{"app_id":"wx_app_id","secret":"secret","mch_id":"1900000001"}"#)?;
+        assert!(config.signer.is_none());
+
+        // 配置了私钥时仍然需要证书序列号
+        let result = WechatV3PayConfigBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .mch_id("1900000001")
+            .private_key_path(fixture("apiclient_key.pem"))
+            .build();
+        assert!(result.is_err());
+        Ok(())
+    }
+
+    /// 测试从环境变量加载配置
+    #[test]
+    fn test_from_env() -> RPayResult<()> {
+        std::env::set_var("RPAY_TEST_APP_ID", "wx_app_id");
+        std::env::set_var("RPAY_TEST_MCH_ID", "1900000001");
+        std::env::set_var("RPAY_TEST_PKCS12_PATH", fixture("apiclient_cert.p12"));
+        // 缺少必填项时返回环境变量名
+        match WechatV3PayConfig::from_env("RPAY_TEST") {
+            Err(RPayError::MissingConfig(field)) => assert_eq!(field, "RPAY_TEST_SECRET"),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        std::env::set_var("RPAY_TEST_SECRET", "secret");
+        let config = WechatV3PayConfig::from_env("RPAY_TEST")?;
+        assert_eq!(config.mch_id, "1900000001");
+        assert!(config.serial_no.is_some());
+        Ok(())
+    }
+}
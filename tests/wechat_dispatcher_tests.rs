@@ -0,0 +1,93 @@
+#[cfg(test)]
+mod tests {
+    use std::{
+        collections::HashMap,
+        sync::{
+            atomic::{AtomicU32, Ordering},
+            Arc,
+        },
+        time::Duration,
+    };
+
+    use rpay::{
+        auth::{
+            token_manager::AccessTokenManagerBuilder,
+            token_store::{CachedToken, MemoryTokenStore, TokenStore},
+        },
+        message::{
+            dispatcher::{DispatchStatus, SubscribeDispatcherBuilder},
+            message::{DataItem, Message, MessageBuilder, Response},
+        },
+        ApiProvider, RPayError, RPayResult,
+    };
+
+    fn api_error(code: &str) -> RPayError {
+        RPayError::Api {
+            provider: ApiProvider::Wechat,
+            http_status: 200,
+            code: code.to_string(),
+            message: "test".to_string(),
+            request_id: None,
+            detail: None,
+        }
+    }
+
+    fn message(touser: &str) -> RPayResult<Message> {
+        let mut data = HashMap::new();
+        data.insert("thing1".to_string(), DataItem { value: "订单已发货".to_string() });
+        MessageBuilder::default()
+            .access_token("")
+            .touser(touser)
+            .template_id("template_id")
+            .data(data)
+            .build()
+            .map_err(|err| RPayError::ErrorWithMsg(err.to_string()))
+    }
+
+    /// 测试批量发送的结果分类和限额重试
+    #[tokio::test]
+    async fn test_dispatch_outcomes() -> RPayResult<()> {
+        let store = Arc::new(MemoryTokenStore::new());
+        store.set("rpay:access_token:wx_app_id", &CachedToken::new("cached", 7200))?;
+        let manager = AccessTokenManagerBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .store(store)
+            .build()?;
+        let dispatcher = SubscribeDispatcherBuilder::default()
+            .token_manager(manager)
+            .concurrency(2)
+            .rate_limit(1000)
+            .backoff(Duration::from_millis(10))
+            .max_retries(2)
+            .build()?;
+
+        let quota_calls = Arc::new(AtomicU32::new(0));
+        let messages = vec![message("ok")?, message("refused")?, message("quota")?, message("limited")?];
+        let report = dispatcher
+            .dispatch_with(messages, |message| {
+                let quota_calls = quota_calls.clone();
+                async move {
+                    assert_eq!(message.access_token, "cached");
+                    match message.touser.as_str() {
+                        "refused" => Err(api_error("43101")),
+                        "quota" if quota_calls.fetch_add(1, Ordering::SeqCst) == 0 => Err(api_error("45009")),
+                        "limited" => Err(api_error("45009")),
+                        _ => Ok(Response { errmsg: "ok".to_string(), errcode: 0 }),
+                    }
+                }
+            })
+            .await;
+
+        let touser: Vec<&str> = report.outcomes.iter().map(|outcome| outcome.touser.as_str()).collect();
+        assert_eq!(touser, vec!["ok", "refused", "quota", "limited"]);
+        assert_eq!(report.sent(), 2);
+        assert_eq!(report.refused(), vec!["refused"]);
+        assert_eq!(report.outcomes[2].attempts, 2);
+        let failed = report.failed();
+        assert_eq!(failed.len(), 1);
+        assert_eq!(failed[0].attempts, 3);
+        assert!(matches!(&failed[0].status, DispatchStatus::Failed(err) if err.api_code() == Some("45009")));
+        Ok(())
+    }
+}
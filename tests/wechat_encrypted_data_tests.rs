@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use aes::Aes128;
+    use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
+    use rpay::{
+        auth::encrypted_data::{check_signature, EncryptedData, EncryptedDataBuilder, PhoneNumberInfo, UserInfo},
+        utils, RPayResult,
+    };
+
+    const APP_ID: &str = "wx4f4bc4dec97d474b";
+    const SESSION_KEY: &str = "tiihtNczf5v6AKRyjwEUhQ==";
+    const IV: &str = "r7BXXKkLb8qrSNn05n0qiA==";
+
+    // 模拟微信使用session_key加密开放数据
+    fn encrypt(plain_text: &str) -> String {
+        let key = utils::base64_decode(SESSION_KEY).unwrap();
+        let iv = utils::base64_decode(IV).unwrap();
+        let cipher = Cbc::<Aes128, Pkcs7>::new_from_slices(&key, &iv).unwrap();
+        utils::base64_encode(cipher.encrypt_vec(plain_text.as_bytes()))
+    }
+
+    /// 测试解密用户信息并校验水印
+    #[test]
+    fn test_decrypt_user_info() -> RPayResult<()> {
+        let timestamp = utils::get_timestamp()? / 1000;
+        let plain_text = format!(
+            r#"{{"openId":"oGZUI0egBJY1zhBYw2KhdUfwVJJE","nickName":"Band","gender":1,"city":"Guangzhou","province":"Guangdong","country":"CN","avatarUrl":"http://wx.qlogo.cn/mmopen/0","unionId":"ocMvos6NjeKLIBqg5Mr9QjxrP1FA","watermark":{{"timestamp":{},"appid":"{}"}}}}"#,
+            timestamp, APP_ID
+        );
+        let data = EncryptedDataBuilder::default()
+            .encrypted_data(encrypt(&plain_text))
+            .iv(IV)
+            .max_age(300)
+            .build()?;
+        let user_info: UserInfo = data.decrypt(SESSION_KEY, APP_ID)?;
+        assert_eq!(user_info.nick_name.as_deref(), Some("Band"));
+        // appid不匹配
+        assert!(data.decrypt::<UserInfo>(SESSION_KEY, "wx_other").is_err());
+        Ok(())
+    }
+
+    /// 测试水印过期
+    #[test]
+    fn test_decrypt_expired() -> RPayResult<()> {
+        let plain_text = format!(
+            r#"{{"phoneNumber":"13580006666","purePhoneNumber":"13580006666","countryCode":"86","watermark":{{"timestamp":1477314187,"appid":"{}"}}}}"#,
+            APP_ID
+        );
+        let mut builder = EncryptedDataBuilder::default();
+        builder.encrypted_data(encrypt(&plain_text)).iv(IV);
+        // 默认校验水印时间
+        assert!(builder.build()?.decrypt::<PhoneNumberInfo>(SESSION_KEY, APP_ID).is_err());
+        assert!(builder.max_age(300).build()?.decrypt::<PhoneNumberInfo>(SESSION_KEY, APP_ID).is_err());
+        let data: serde_json::Value = serde_json::json!({"encryptedData": encrypt(&plain_text), "iv": IV});
+        let data: EncryptedData = serde_json::from_value(data)?;
+        assert_eq!(data.max_age, 7200);
+        assert!(data.decrypt::<PhoneNumberInfo>(SESSION_KEY, APP_ID).is_err());
+        // 0表示不校验时间
+        let phone: PhoneNumberInfo = builder.max_age(0).build()?.decrypt(SESSION_KEY, APP_ID)?;
+        assert_eq!(phone.country_code, "86");
+        Ok(())
+    }
+
+    /// 测试rawData签名
+    #[test]
+    fn test_check_signature() {
+        let raw_data = r#"{"nickName":"Band","gender":1}"#;
+        let signature = utils::sha1_hex(format!("{}{}", raw_data, SESSION_KEY));
+        assert!(check_signature(raw_data, SESSION_KEY, &signature));
+        assert!(!check_signature(raw_data, "other", &signature));
+    }
+}
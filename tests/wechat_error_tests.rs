@@ -0,0 +1,37 @@
+#[cfg(test)]
+mod tests {
+
+    use rpay::{lookup_error_code, ApiProvider, RPayError};
+
+    fn api_error(provider: ApiProvider, http_status: u16, code: &str) -> RPayError {
+        RPayError::Api {
+            provider,
+            http_status,
+            code: code.to_string(),
+            message: "test".to_string(),
+            request_id: None,
+            detail: None,
+        }
+    }
+
+    /// 测试错误码目录
+    #[test]
+    fn test_lookup_error_code() {
+        let info = lookup_error_code(ApiProvider::WechatPay, "SYSTEM_ERROR").unwrap();
+        assert!(info.retryable);
+        let info = lookup_error_code(ApiProvider::Wechat, "43101").unwrap();
+        assert!(!info.retryable);
+        assert!(lookup_error_code(ApiProvider::Wechat, "SYSTEM_ERROR").is_none());
+    }
+
+    /// 测试是否可以重试
+    #[test]
+    fn test_is_retryable() {
+        assert!(api_error(ApiProvider::Wechat, 200, "42001").is_retryable());
+        assert!(api_error(ApiProvider::Wechat, 200, "42001").is_token_expired());
+        assert!(!api_error(ApiProvider::WechatPay, 400, "PARAM_ERROR").is_retryable());
+        // 目录中没有的错误码按状态码判断
+        assert!(api_error(ApiProvider::WechatPay, 503, "UNKNOWN").is_retryable());
+        assert!(!api_error(ApiProvider::WechatPay, 403, "UNKNOWN").is_retryable());
+    }
+}
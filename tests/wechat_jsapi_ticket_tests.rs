@@ -0,0 +1,40 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use rpay::{
+        auth::{
+            jsapi_ticket::{js_sdk_signature, JsapiTicketManager},
+            token_manager::AccessTokenManagerBuilder,
+            token_store::{CachedToken, MemoryTokenStore, TokenStore},
+        },
+        RPayResult,
+    };
+
+    /// 测试JS-SDK签名，使用官方文档中的示例
+    #[test]
+    fn test_js_sdk_signature() {
+        let ticket = "sM4AOVdWfPE4DxkXGEs8VMCPGGVi4C3VM0P37wVUCFvkVAy_90u5h9nbSlYy3-Sl-HhTdfl2fzFy1AOcHKP7qg";
+        let signature = js_sdk_signature(ticket, "Wm3WZYTPz0wzccnW", 1414587457, "http://mp.weixin.qq.com?params=value#top");
+        assert_eq!(signature, "0f9de62fce790f9a083d5c99e95740ceb90c27ed");
+    }
+
+    /// 测试使用缓存的jsapi_ticket生成wx.config配置
+    #[tokio::test]
+    async fn test_js_sdk_config() -> RPayResult<()> {
+        let store = Arc::new(MemoryTokenStore::new());
+        store.set("rpay:jsapi_ticket:wx_app_id", &CachedToken::new("ticket", 7200))?;
+        let tokens = AccessTokenManagerBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .store(store)
+            .build()?;
+        let config = JsapiTicketManager::new(tokens).js_sdk_config("https://example.com/pay").await?;
+        assert_eq!(config.app_id, "wx_app_id");
+        assert_eq!(
+            config.signature,
+            js_sdk_signature("ticket", &config.nonce_str, config.timestamp, "https://example.com/pay")
+        );
+        Ok(())
+    }
+}
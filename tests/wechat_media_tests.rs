@@ -0,0 +1,71 @@
+#[cfg(test)]
+mod tests {
+
+    use dotenvy::dotenv;
+    use rpay::{
+        media::{
+            MaterialBuilder, MaterialType, MediaFile, MediaType, TempMediaBuilder, UploadMediaResponse,
+            VideoDescription,
+        },
+        RPayResult,
+    };
+
+    // 获取access_token，请通过auth模块获取,将其保存配置文，注意有效期2小时，不要频繁调用
+    fn get_access_token() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_ACCESS_TOKEN").expect("WECHAT_ACCESS_TOKEN not found")
+    }
+
+    // 上传测试用的图片文件
+    fn get_media_path() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_MEDIA_PATH").expect("WECHAT_MEDIA_PATH not found")
+    }
+
+    /// 测试读取本地文件和解析上传结果
+    #[test]
+    fn test_media_file() -> RPayResult<()> {
+        let file = MediaFile::from_path("tests/fixtures/apiclient_cert.pem")?.content_type("application/x-pem-file");
+        assert_eq!(file.file_name, "apiclient_cert.pem");
+        assert!(!file.content.is_empty());
+        file.part()?;
+        assert!(MediaFile::new("a.jpg", "x").content_type("invalid content type").part().is_err());
+
+        let resp: UploadMediaResponse =
+            serde_json::from_str(r#"{"type":"thumb","thumb_media_id":"MEDIA_ID","created_at":1700000000}"#)?;
+        assert_eq!(resp.media_type, Some(MediaType::Thumb));
+        assert_eq!(resp.media_id.as_deref(), Some("MEDIA_ID"));
+        Ok(())
+    }
+
+    /// 测试上传视频永久素材时必须提供描述信息
+    #[tokio::test]
+    async fn test_video_description_required() -> RPayResult<()> {
+        let material = MaterialBuilder::default().access_token("token").build()?;
+        let file = MediaFile::new("video.mp4", vec![0u8; 16]);
+        assert!(material.add(MediaType::Video, &file, None).await.is_err());
+        let description = VideoDescription { title: "标题".to_string(), introduction: "描述".to_string() };
+        assert_eq!(serde_json::to_string(&description)?, r#"{"title":"标题","introduction":"描述"}"#);
+        Ok(())
+    }
+
+    /// 上传并获取临时素材
+    #[tokio::test]
+    async fn test_temp_media() -> RPayResult<()> {
+        let media = TempMediaBuilder::default().access_token(get_access_token()).build()?;
+        let resp = media.upload(MediaType::Image, &MediaFile::from_path(get_media_path())?).await?;
+        println!("resp => {:?}", resp);
+        let content = media.get(&resp.media_id.unwrap_or_default()).await?;
+        println!("size => {}", content.len());
+        Ok(())
+    }
+
+    /// 获取永久素材总数和图片素材列表
+    #[tokio::test]
+    async fn test_material_list() -> RPayResult<()> {
+        let material = MaterialBuilder::default().access_token(get_access_token()).build()?;
+        println!("count => {:?}", material.count().await?);
+        println!("images => {:?}", material.batch_get(MaterialType::Image, 0, 20).await?);
+        Ok(())
+    }
+}
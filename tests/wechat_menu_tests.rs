@@ -0,0 +1,149 @@
+#[cfg(test)]
+mod tests {
+
+    use dotenvy::dotenv;
+    use rpay::{
+        menu::{Button, ButtonAction, CustomMenuBuilder, GetMenuResponse, MatchRuleBuilder, Menu},
+        message::inbound::{xml_to_map, InboundEvent, InboundMessage},
+        RPayResult,
+    };
+    use serde_json::json;
+
+    // 获取access_token，请通过auth模块获取,将其保存配置文，注意有效期2小时，不要频繁调用
+    fn get_access_token() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_ACCESS_TOKEN").expect("WECHAT_ACCESS_TOKEN not found")
+    }
+
+    fn get_open_id() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_OPEN_ID").expect("WECHAT_OPEN_ID not found")
+    }
+
+    fn menu() -> Menu {
+        Menu::new(vec![
+            Button::click("今日歌曲", "V1001_TODAY_MUSIC"),
+            Button::sub(
+                "菜单",
+                vec![
+                    Button::view("搜索", "http://www.soso.com/"),
+                    Button::miniprogram("wxa", "wx286b93c14bbf93aa", "pages/lunar/index", "http://mp.weixin.qq.com"),
+                    Button::new("扫码", ButtonAction::ScancodePush { key: "rselfmenu_0_1".to_string() }),
+                    Button::new("发送位置", ButtonAction::LocationSelect { key: "rselfmenu_2_0".to_string() }),
+                ],
+            ),
+            Button::new("文章", ButtonAction::ArticleId { article_id: "ARTICLE_ID".to_string() }),
+        ])
+    }
+
+    /// 测试菜单的序列化和查询结果的解析
+    #[test]
+    fn test_menu_json() -> RPayResult<()> {
+        let value = serde_json::to_value(menu())?;
+        assert_eq!(value["button"][0], json!({"type": "click", "name": "今日歌曲", "key": "V1001_TODAY_MUSIC"}));
+        assert_eq!(value["button"][1]["name"], "菜单");
+        assert!(value["button"][1].get("type").is_none());
+        assert_eq!(value["button"][1]["sub_button"][1]["pagepath"], "pages/lunar/index");
+        assert_eq!(value["button"][1]["sub_button"][2]["type"], "scancode_push");
+        assert_eq!(value["button"][2], json!({"type": "article_id", "name": "文章", "article_id": "ARTICLE_ID"}));
+        assert!(value.get("matchrule").is_none());
+
+        let rule = MatchRuleBuilder::default().tag_id("2").client_platform_type("2").build()?;
+        let value = serde_json::to_value(Menu::conditional(vec![Button::click("今日歌曲", "KEY")], rule))?;
+        assert_eq!(value["matchrule"], json!({"tag_id": "2", "client_platform_type": "2"}));
+
+        let resp: GetMenuResponse = serde_json::from_value(json!({
+            "menu": {
+                "button": [
+                    {"type": "click", "name": "今日歌曲", "key": "V1001_TODAY_MUSIC", "sub_button": []},
+                    {"name": "菜单", "sub_button": [{"type": "view", "name": "搜索", "url": "http://www.soso.com/", "sub_button": []}]}
+                ],
+                "menuid": 208396938
+            },
+            "conditionalmenu": [{
+                "button": [{"type": "media_id", "name": "图片", "media_id": "MEDIA_ID", "sub_button": []}],
+                "matchrule": {"tag_id": "2", "sex": "1", "client_platform_type": "2"},
+                "menuid": 208396993
+            }]
+        }))?;
+        let default_menu = resp.menu.unwrap();
+        assert_eq!(
+            default_menu.button[0].action,
+            Some(ButtonAction::Click { key: "V1001_TODAY_MUSIC".to_string() })
+        );
+        assert!(default_menu.button[1].action.is_none());
+        assert_eq!(
+            default_menu.button[1].sub_button[0].action,
+            Some(ButtonAction::View { url: "http://www.soso.com/".to_string() })
+        );
+        assert_eq!(resp.conditionalmenu[0].matchrule.as_ref().unwrap().sex.as_deref(), Some("1"));
+        Ok(())
+    }
+
+    /// 测试解析菜单产生的事件推送
+    #[test]
+    fn test_menu_events() -> RPayResult<()> {
+        let header = "<ToUserName><![CDATA[gh_123]]></ToUserName><FromUserName><![CDATA[oUser]]></FromUserName><CreateTime>1408090502</CreateTime><MsgType><![CDATA[event]]></MsgType>";
+        let parse = |body: &str| InboundMessage::from_map(xml_to_map(&format!("<xml>{}{}</xml>", header, body))?);
+
+        match parse("<Event><![CDATA[VIEW]]></Event><EventKey><![CDATA[http://www.soso.com/]]></EventKey><MenuId>208396993</MenuId>")? {
+            InboundMessage::Event(InboundEvent::View(event)) => {
+                assert_eq!(event.event_key, "http://www.soso.com/");
+                assert_eq!(event.menu_id.as_deref(), Some("208396993"));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<Event><![CDATA[scancode_waitmsg]]></Event><EventKey><![CDATA[6]]></EventKey><ScanCodeInfo><ScanType><![CDATA[qrcode]]></ScanType><ScanResult><![CDATA[2]]></ScanResult></ScanCodeInfo>")? {
+            InboundMessage::Event(InboundEvent::ScancodeWaitmsg(event)) => {
+                assert_eq!(event.scan_code_info.scan_type, "qrcode");
+                assert_eq!(event.scan_code_info.scan_result, "2");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        // 单张图片为对象，多张为数组
+        match parse("<Event><![CDATA[pic_sysphoto]]></Event><EventKey><![CDATA[6]]></EventKey><SendPicsInfo><Count>1</Count><PicList><item><PicMd5Sum><![CDATA[1b5f7c23b5bf75682a53e7b6d163e185]]></PicMd5Sum></item></PicList></SendPicsInfo>")? {
+            InboundMessage::Event(InboundEvent::PicSysphoto(event)) => {
+                assert_eq!(event.send_pics_info.count, 1);
+                assert_eq!(event.send_pics_info.pic_list.item[0].pic_md5_sum, "1b5f7c23b5bf75682a53e7b6d163e185");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<Event><![CDATA[pic_weixin]]></Event><EventKey><![CDATA[6]]></EventKey><SendPicsInfo><Count>2</Count><PicList><item><PicMd5Sum><![CDATA[a]]></PicMd5Sum></item><item><PicMd5Sum><![CDATA[b]]></PicMd5Sum></item></PicList></SendPicsInfo>")? {
+            InboundMessage::Event(InboundEvent::PicWeixin(event)) => assert_eq!(event.send_pics_info.pic_list.item.len(), 2),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<Event><![CDATA[location_select]]></Event><EventKey><![CDATA[6]]></EventKey><SendLocationInfo><Location_X><![CDATA[23]]></Location_X><Location_Y><![CDATA[113]]></Location_Y><Scale><![CDATA[15]]></Scale><Label><![CDATA[ 广州市海珠区客村艺苑路 106号]]></Label><Poiname><![CDATA[]]></Poiname></SendLocationInfo>")? {
+            InboundMessage::Event(InboundEvent::LocationSelect(event)) => {
+                assert_eq!(event.send_location_info.location_x, 23.0);
+                assert_eq!(event.send_location_info.scale, 15);
+                assert_eq!(event.header.from_user_name, "oUser");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<Event><![CDATA[view_miniprogram]]></Event><EventKey><![CDATA[pages/index/index]]></EventKey><MenuId>MENUID</MenuId>")? {
+            InboundMessage::Event(InboundEvent::ViewMiniprogram(event)) => assert_eq!(event.event_key, "pages/index/index"),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        Ok(())
+    }
+
+    /// 创建并查询默认菜单
+    #[tokio::test]
+    async fn test_create_menu() -> RPayResult<()> {
+        let custom_menu = CustomMenuBuilder::default().access_token(get_access_token()).build()?;
+        let resp = custom_menu.create(&menu()).await?;
+        println!("resp => {:?}", resp);
+        let resp = custom_menu.get().await?;
+        println!("menu => {:?}", resp);
+        Ok(())
+    }
+
+    /// 测试个性化菜单匹配结果
+    #[tokio::test]
+    async fn test_try_match() -> RPayResult<()> {
+        let custom_menu = CustomMenuBuilder::default().access_token(get_access_token()).build()?;
+        let resp = custom_menu.try_match(&get_open_id()).await?;
+        println!("resp => {:?}", resp);
+        Ok(())
+    }
+}
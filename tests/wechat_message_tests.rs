@@ -4,7 +4,7 @@ mod tests {
 
     use dotenvy::dotenv;
     use rpay::{
-        message::{message::{DataItem, MessageBuilder}, CustomerServiceBuilder, Link, Miniprogrampage, MsgType, Text}, pay::config::{WechatV3PayConfig, WechatV3PayConfigBuilder}, RPayError, RPayResult
+        message::{message::{DataItem, MessageBuilder}, subscribe::{validate_data, KeywordRule, SubscribeTemplate}, CustomerServiceBuilder, CustomServiceAccount, Link, Miniprogrampage, MsgMenu, MsgMenuItem, MsgType, Text, TypingCommand}, pay::config::WechatV3PayConfig, RPayError, RPayResult
     };
 
     // 获取用的的openid
@@ -86,24 +86,7 @@ mod tests {
     #[allow(unused)]
     fn get_sdk() -> RPayResult<WechatV3PayConfig> {
         dotenv().ok();
-        let app_id = std::env::var("WECHAT_APP_ID").expect("WECHAT_APP_ID not found");
-        let mch_id = std::env::var("WECHAT_MCH_ID").expect("WECHAT_MCH_ID not found");
-        let secret = std::env::var("WECHAT_SECRET").expect("WECHAT_SECRET not found");
-        let private_key =
-            std::env::var("WECHAT_PRIVATE_KEY").expect("WECHAT_PRIVATE_KEY not found");
-        let serial_no = std::env::var("WECHAT_SERIAL_NO").expect("WECHAT_SERIAL_NO not found");
-        let v3_key = std::env::var("WECHAT_KEY_V3").expect("WECHAT_KEY_V3 not found");
-        let notify_url = std::env::var("WECHAT_NOTIFY_URL").expect("WECHAT_NOTIFY_URL not found");
-        let sdk = WechatV3PayConfigBuilder::default()
-            .app_id(app_id)
-            .secret(secret)
-            .api_key_v3(v3_key)
-            .serial_no(serial_no.to_string())
-            .mch_id(mch_id)
-            .private_key(private_key)
-            .notify_url(notify_url)
-            .build()?;
-        Ok(sdk)
+        WechatV3PayConfig::from_env("WECHAT")
     }
 
     /// 测试发送发货消息
@@ -236,5 +219,97 @@ mod tests {
          println!("resp => {:?}", resp);
          Ok(())
      }
- 
+
+    /// 测试关键词规则校验和截断
+    #[test]
+    fn test_keyword_rule() -> RPayResult<()> {
+        assert_eq!(KeywordRule::from_keyword("character_string12")?, KeywordRule::CharacterString);
+        assert!(KeywordRule::from_keyword("unknown1").is_err());
+        assert!(KeywordRule::Thing.validate("一二三四五六七八九十一二三四五六七八九十").is_ok());
+        assert!(KeywordRule::Thing.validate("一二三四五六七八九十一二三四五六七八九十一").is_err());
+        assert!(KeywordRule::Number.validate("18.00").is_ok());
+        assert!(KeywordRule::Number.validate("1a").is_err());
+        assert!(KeywordRule::Time.validate("2024-06-21 14:16:33").is_ok());
+        assert!(KeywordRule::Time.validate("明天").is_err());
+        assert!(KeywordRule::Amount.validate("¥18.00").is_ok());
+        assert!(KeywordRule::Amount.validate("18元").is_ok());
+        assert!(KeywordRule::Phrase.validate("已发货").is_ok());
+        assert!(KeywordRule::Phrase.validate("shipped").is_err());
+        assert!(KeywordRule::CarNumber.validate("粤A12345").is_ok());
+        assert!(KeywordRule::Name.validate("一二三四五六七八九十一").is_err());
+        assert_eq!(KeywordRule::Phrase.truncate("一二三四五六"), "一二三四五");
+        assert_eq!(KeywordRule::Number.truncate("123"), "123");
+        Ok(())
+    }
+
+    /// 测试按模板生成经过校验的数据
+    #[test]
+    fn test_subscribe_data() -> RPayResult<()> {
+        let template = SubscribeTemplate::new("rc-r-FZ6gwiq2tvkWwJeFckWRkU-RmReKyeFfkfFQLs", &["thing1", "amount2", "number3", "time4"])?;
+        let mut data = template.data();
+        data.set("thing1", "大洋芋大洋芋大洋芋大洋芋大洋芋大洋芋大洋芋")
+            .set("amount2", "18.00")
+            .set("number3", "1")
+            .set("time4", "2024-06-21 14:16:33");
+        assert!(data.build().is_err());
+        let items = data.truncate(true).build()?;
+        assert_eq!(items["thing1"].value.chars().count(), 20);
+        validate_data(&items)?;
+
+        let message = MessageBuilder::default()
+            .access_token("token")
+            .touser("oUser")
+            .subscribe_data(&data)?
+            .build()
+            .map_err(|err|RPayError::ErrorWithMsg(err.to_string()))?;
+        assert_eq!(message.template_id, template.template_id);
+
+        // 缺少关键词和多余的关键词
+        let mut data = template.data();
+        data.set("thing1", "大洋芋");
+        assert!(data.build().is_err());
+        data.set("amount2", "18.00").set("number3", "1").set("time4", "14:16").set("thing5", "多余");
+        assert!(data.build().is_err());
+        Ok(())
+    }
+
+    /// 测试客服菜单消息参数
+    #[test]
+    fn test_kf_msgmenu_params() -> RPayResult<()> {
+        let message = CustomerServiceBuilder::default()
+            .access_token("token")
+            .touser("oUser")
+            .msg_type(MsgType::Msgmenu)
+            .msgmenu(MsgMenu {
+                head_content: "您对本次服务是否满意呢? ".to_string(),
+                list: vec![
+                    MsgMenuItem { id: "101".to_string(), content: "满意".to_string() },
+                    MsgMenuItem { id: "102".to_string(), content: "不满意".to_string() },
+                ],
+                tail_content: "欢迎再次光临".to_string(),
+            })
+            .customservice(CustomServiceAccount { kf_account: "test1@kftest".to_string() })
+            .build()?;
+        let body = serde_json::to_value(&message)?;
+        assert_eq!(body["msgtype"], "msgmenu");
+        assert_eq!(body["msgmenu"]["list"][1]["id"], "102");
+        assert_eq!(body["customservice"]["kf_account"], "test1@kftest");
+        assert!(body.get("access_token").is_none());
+        assert!(body.get("text").is_none());
+        Ok(())
+    }
+
+    /// 测试 - 客服输入状态
+    #[tokio::test]
+    async fn test_kf_typing() -> RPayResult<()> {
+        let resp = CustomerServiceBuilder::default()
+            .access_token(get_access_token())
+            .touser(get_oepn_id())
+            .msg_type(MsgType::Text)
+            .build()?
+            .typing(TypingCommand::Typing)
+            .await?;
+        println!("resp => {:?}", resp);
+        Ok(())
+    }
 }
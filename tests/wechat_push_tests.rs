@@ -0,0 +1,203 @@
+#[cfg(test)]
+mod tests {
+
+    use rpay::{
+        message::{
+            inbound::{xml_to_map, InboundEvent, InboundMessage, PushFormat},
+            push::{PushQuery, PushServer, PushServerBuilder},
+            reply::{ArticleBuilder, Reply, ReplyBuilder, ReplyContent},
+        },
+        RPayResult,
+    };
+
+    const TOKEN: &str = "test_token";
+    const APP_ID: &str = "wx5823bf96d3bd56c7";
+    const ENCODING_AES_KEY: &str = "abcdefghijklmnopqrstuvwxyz0123456789ABCDEFG";
+    const TIMESTAMP: &str = "1409659813";
+    const NONCE: &str = "1372623149";
+    // 使用openssl按WXBizMsgCrypt格式加密的文本消息
+    const ENCRYPT: &str = "8Q6sFaw1Cb9qj7RuhNb60uqUrxESj9kLRQtlOOBlG9zjMsLTxSP3RoQV+PbnT/1BlwXl2BH1T7meHr7TfVaGYUr19jI9PrbBmEsA8p72T7FJ6Y7q1rbAErwXSaiWnNGEqGGsGZqF5Ebg9G0h8dIzEXbLXWAFSDxmGvBUv2Deg5PQTChTlYZXE2eTLJjbBOU1Jqk4xLlGtcN3pAcpQvIkncCohQah++NkV5TKdlNMm9NXqnqkTIwRE7vVLztGJg9N783iEBrqXIrr6dHjC0/qMWIC9/XXO4CskHqvZI6VAbjnIn4EBk1A/UwFsKtMjGWeEkY08rJe9a1TK6xVQhe843046zfmA+tbhfp7emC/yJuKIXJcK+WyLBgr8shP4pzI";
+
+    fn server() -> RPayResult<PushServer> {
+        Ok(PushServerBuilder::default()
+            .token(TOKEN)
+            .app_id(APP_ID)
+            .encoding_aes_key(ENCODING_AES_KEY)
+            .build()?)
+    }
+
+    fn query() -> PushQuery {
+        PushQuery {
+            signature: String::from("2bc0450f0f537460daffd39c075509fec6bd8339"),
+            timestamp: String::from(TIMESTAMP),
+            nonce: String::from(NONCE),
+            ..Default::default()
+        }
+    }
+
+    /// 测试服务器地址验证
+    #[test]
+    fn test_verify_url() -> RPayResult<()> {
+        let mut query = query();
+        query.echostr = Some(String::from("echo"));
+        assert_eq!(server()?.verify_url(&query)?, "echo");
+        query.signature = String::from("invalid");
+        assert!(server()?.verify_url(&query).is_err());
+        Ok(())
+    }
+
+    /// 测试解析明文XML和JSON消息
+    #[test]
+    fn test_parse_plain() -> RPayResult<()> {
+        let xml = "<xml><ToUserName><![CDATA[gh_123]]></ToUserName><FromUserName><![CDATA[oUser]]></FromUserName><CreateTime>1409659813</CreateTime><MsgType><![CDATA[text]]></MsgType><Content><![CDATA[1 < 2]]></Content><MsgId>1234567890123456</MsgId></xml>";
+        match server()?.parse(&query(), xml)? {
+            InboundMessage::Text(message) => {
+                assert_eq!(message.content, "1 < 2");
+                assert_eq!(message.header.create_time, 1409659813);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        let json = r#"{"ToUserName":"gh_123","FromUserName":"oUser","CreateTime":1409659813,"MsgType":"event","Event":"user_enter_tempsession","SessionFrom":"wxapp"}"#;
+        match server()?.parse(&query(), json)? {
+            InboundMessage::Event(InboundEvent::Other(message)) => {
+                assert_eq!(message.event, "user_enter_tempsession");
+                assert_eq!(message.fields["SessionFrom"], "wxapp");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        Ok(())
+    }
+
+    /// 测试解析各类型消息和事件
+    #[test]
+    fn test_parse_typed() -> RPayResult<()> {
+        let header = "<ToUserName><![CDATA[gh_123]]></ToUserName><FromUserName><![CDATA[oUser]]></FromUserName><CreateTime>1409659813</CreateTime>";
+        let parse = |body: &str| InboundMessage::from_map(xml_to_map(&format!("<xml>{}{}</xml>", header, body))?);
+
+        match parse("<MsgType><![CDATA[location]]></MsgType><Location_X>23.134521</Location_X><Location_Y>113.358803</Location_Y><Scale>20</Scale><Label><![CDATA[位置信息]]></Label><MsgId>1234567890123456</MsgId>")? {
+            InboundMessage::Location(message) => {
+                assert_eq!(message.location_x, 23.134521);
+                assert_eq!(message.scale, 20);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<MsgType><![CDATA[event]]></MsgType><Event><![CDATA[subscribe]]></Event><EventKey><![CDATA[qrscene_123123]]></EventKey><Ticket><![CDATA[TICKET]]></Ticket>")? {
+            InboundMessage::Event(InboundEvent::Subscribe(event)) => assert_eq!(event.scene(), Some("123123")),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<MsgType><![CDATA[event]]></MsgType><Event><![CDATA[CLICK]]></Event><EventKey><![CDATA[V1001_TODAY_MUSIC]]></EventKey>")? {
+            InboundMessage::Event(InboundEvent::Click(event)) => assert_eq!(event.event_key, "V1001_TODAY_MUSIC"),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<MsgType><![CDATA[event]]></MsgType><Event><![CDATA[TEMPLATESENDJOBFINISH]]></Event><MsgID>200163836</MsgID><Status><![CDATA[success]]></Status>")? {
+            InboundMessage::Event(InboundEvent::TemplateSendJobFinish(event)) => assert_eq!(event.msg_id, 200163836),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        // 单条订阅结果为对象，多条为数组
+        match parse("<MsgType><![CDATA[event]]></MsgType><Event><![CDATA[subscribe_msg_popup_event]]></Event><SubscribeMsgPopupEvent><List><TemplateId><![CDATA[VRR0UEO9VJOLs0MHlU0OilqX6MVFDwH3_3gz3Oc0NIc]]></TemplateId><SubscribeStatusString><![CDATA[accept]]></SubscribeStatusString><PopupScene>2</PopupScene></List></SubscribeMsgPopupEvent>")? {
+            InboundMessage::Event(InboundEvent::SubscribeMsgPopup(event)) => {
+                assert_eq!(event.list.len(), 1);
+                assert_eq!(event.list[0].subscribe_status_string, "accept");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        let json = r#"{"ToUserName":"gh_123","FromUserName":"oUser","CreateTime":1409659813,"MsgType":"event","Event":"subscribe_msg_sent_event","List":[{"TemplateId":"t1","MsgID":"1700827132819554304","ErrorCode":0,"ErrorStatus":"success"},{"TemplateId":"t2","MsgID":"1700827132819554305","ErrorCode":10005,"ErrorStatus":"fail"}]}"#;
+        match server()?.parse(&query(), json)? {
+            InboundMessage::Event(InboundEvent::SubscribeMsgSent(event)) => {
+                assert_eq!(event.list.len(), 2);
+                assert_eq!(event.list[1].error_code, 10005);
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        match parse("<MsgType><![CDATA[miniprogrampage]]></MsgType><Title><![CDATA[title]]></Title>")? {
+            InboundMessage::Unknown(header, fields) => {
+                assert_eq!(header.msg_type, "miniprogrampage");
+                assert_eq!(fields["Title"], "title");
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        Ok(())
+    }
+
+    /// 测试解析安全模式消息
+    #[test]
+    fn test_parse_encrypted() -> RPayResult<()> {
+        let mut query = query();
+        query.encrypt_type = Some(String::from("aes"));
+        query.msg_signature = Some(String::from("05f8d2ad3ea176967fd87c3661f81aeb2769f953"));
+        let body = format!("<xml><ToUserName><![CDATA[gh_123]]></ToUserName><Encrypt><![CDATA[{}]]></Encrypt></xml>", ENCRYPT);
+        match server()?.parse(&query, &body)? {
+            InboundMessage::Text(message) => assert_eq!(message.content, "hello"),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        // appid不匹配
+        let mut other = server()?;
+        other.app_id = String::from("wx_other");
+        assert!(other.parse(&query, &body).is_err());
+        // 消息体签名错误
+        query.msg_signature = Some(String::from("invalid"));
+        assert!(server()?.parse(&query, &body).is_err());
+        Ok(())
+    }
+
+    /// 测试加密回复后可以解密
+    #[test]
+    fn test_encrypt_reply() -> RPayResult<()> {
+        let server = server()?;
+        let reply = "<xml><Content><![CDATA[你好]]></Content></xml>";
+        let body = server.encrypt_reply(reply, PushFormat::Xml, TIMESTAMP, NONCE)?;
+        let fields = xml_to_map(&body)?;
+        let encrypt = fields["Encrypt"].as_str().unwrap();
+        assert_eq!(server.decrypt(encrypt)?, reply);
+        Ok(())
+    }
+
+    /// 测试生成被动回复
+    #[test]
+    fn test_reply() -> RPayResult<()> {
+        let reply = ReplyBuilder::default()
+            .to_user_name("oUser")
+            .from_user_name("gh_123")
+            .create_time(1409659813)
+            .content(ReplyContent::Text(String::from("a]]>b")))
+            .build()?;
+        assert_eq!(
+            reply.to_xml(),
+            "<xml><ToUserName><![CDATA[oUser]]></ToUserName><FromUserName><![CDATA[gh_123]]></FromUserName><CreateTime>1409659813</CreateTime><MsgType><![CDATA[text]]></MsgType><Content><![CDATA[a]]]]><![CDATA[>b]]></Content></xml>"
+        );
+        let fields = xml_to_map(&reply.to_xml())?;
+        assert_eq!(fields["Content"], "a]]>b");
+
+        let article = ArticleBuilder::default().title("标题").url("https://example.com").build()?;
+        let reply = Reply { content: ReplyContent::News(vec![article]), ..reply };
+        let fields = xml_to_map(&reply.to_xml())?;
+        assert_eq!(fields["ArticleCount"], "1");
+        assert_eq!(fields["Articles"]["item"]["Title"], "标题");
+
+        let reply = Reply { content: ReplyContent::TransferCustomerService(Some(String::from("test1@test"))), ..reply };
+        let fields: serde_json::Value = serde_json::from_str(&reply.to_json())?;
+        assert_eq!(fields["MsgType"], "transfer_customer_service");
+        assert_eq!(fields["TransInfo"]["KfAccount"], "test1@test");
+        Ok(())
+    }
+
+    /// 测试安全模式下加密被动回复
+    #[test]
+    fn test_render_encrypted_reply() -> RPayResult<()> {
+        let server = server()?;
+        let mut query = query();
+        let header = match server.parse(&query, "<xml><ToUserName><![CDATA[gh_123]]></ToUserName><FromUserName><![CDATA[oUser]]></FromUserName><CreateTime>1409659813</CreateTime><MsgType><![CDATA[image]]></MsgType><PicUrl><![CDATA[url]]></PicUrl><MediaId><![CDATA[media]]></MediaId><MsgId>1</MsgId></xml>")? {
+            InboundMessage::Image(message) => message.header,
+            other => panic!("unexpected message: {:?}", other),
+        };
+        let reply = Reply::to(&header, ReplyContent::Image(String::from("media")));
+        assert_eq!(reply.to_user_name, "oUser");
+        assert_eq!(reply.render(&server, &query, PushFormat::Xml)?, reply.to_xml());
+
+        query.encrypt_type = Some(String::from("aes"));
+        let body = reply.render(&server, &query, PushFormat::Xml)?;
+        let fields = xml_to_map(&body)?;
+        assert_eq!(server.decrypt(fields["Encrypt"].as_str().unwrap())?, reply.to_xml());
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+#[cfg(test)]
+mod tests {
+
+    use aes_gcm::{
+        aead::{Aead, Payload},
+        Aes256Gcm, KeyInit,
+    };
+    use rpay::{
+        pay::{
+            config::{WechatV3PayConfig, WechatV3PayConfigBuilder},
+            parse_encrypt::ParseEncryptBuilder,
+            registry::MerchantRegistry,
+        },
+        utils, RPayResult,
+    };
+
+    const NONCE: &str = "fdasflkja484";
+    const ASSOCIATED_DATA: &str = "transaction";
+
+    fn fixture(name: &str) -> String {
+        format!("{}/tests/fixtures/{}", env!("CARGO_MANIFEST_DIR"), name)
+    }
+
+    fn config(mch_id: &str, app_id: &str, api_key_v3: &str) -> RPayResult<WechatV3PayConfig> {
+        Ok(WechatV3PayConfigBuilder::default()
+            .app_id(app_id)
+            .secret("secret")
+            .mch_id(mch_id)
+            .api_key_v3(api_key_v3)
+            .private_key_path(fixture("apiclient_key.pem"))
+            .certificate_path(fixture("apiclient_cert.pem"))
+            .build()?)
+    }
+
+    // 模拟微信支付使用APIv3密钥加密回调资源
+    fn encrypt(api_key_v3: &str, plain_text: &str) -> String {
+        let cipher = Aes256Gcm::new(api_key_v3.as_bytes().into());
+        let payload = Payload {
+            msg: plain_text.as_bytes(),
+            aad: ASSOCIATED_DATA.as_bytes(),
+        };
+        utils::base64_encode(cipher.encrypt(NONCE.as_bytes().into(), payload).unwrap())
+    }
+
+    /// 测试按商户号和app_id查找商户，以及热更新配置
+    #[test]
+    fn test_lookup_and_reload() -> RPayResult<()> {
+        let registry = MerchantRegistry::new();
+        registry.insert(config("1900000001", "wx_app_1", "a".repeat(32).as_str())?)?;
+        registry.insert(config("1900000002", "wx_app_2", "b".repeat(32).as_str())?)?;
+        registry.bind_app_id("wx_app_3", "1900000002");
+        assert_eq!(registry.get_by_app_id("wx_app_3").unwrap().mch_id(), "1900000002");
+        assert!(registry.get_by_mch_id("1900000001").unwrap().config.signer.is_some());
+
+        // 替换1900000001的app_id，并移除1900000002
+        registry.reload(vec![config("1900000001", "wx_app_new", "a".repeat(32).as_str())?])?;
+        assert_eq!(registry.len(), 1);
+        assert!(registry.get_by_app_id("wx_app_1").is_none());
+        assert!(registry.get_by_app_id("wx_app_3").is_none());
+        assert_eq!(registry.get_by_app_id("wx_app_new").unwrap().mch_id(), "1900000001");
+
+        // 任何一个配置错误时注册表保持不变
+        let mut invalid = config("1900000003", "wx_app_3", "c".repeat(32).as_str())?;
+        invalid.private_key = Some(String::from("invalid"));
+        invalid.signer = None;
+        assert!(registry
+            .reload(vec![config("1900000002", "wx_app_2", "b".repeat(32).as_str())?, invalid])
+            .is_err());
+        assert_eq!(registry.mch_ids(), vec!["1900000001".to_string()]);
+        Ok(())
+    }
+
+    /// 测试按平台证书序列号和解密结果路由回调通知
+    #[test]
+    fn test_route_notification() -> RPayResult<()> {
+        let key_1 = "a".repeat(32);
+        let key_2 = "b".repeat(32);
+        let registry = MerchantRegistry::new();
+        registry.insert(config("1900000001", "wx_app_1", &key_1)?)?;
+        registry.insert(config("1900000002", "wx_app_2", &key_2)?)?;
+        let pem = std::fs::read(fixture("apiclient_cert.pem"))?;
+        let serial_no = registry.add_platform_certificate("1900000002", pem)?;
+
+        let resource = |key: &str, mch_id: &str| {
+            ParseEncryptBuilder::default()
+                .ciphertext(encrypt(key, &format!(r#"{{"mchid":"{}","out_trade_no":"T001"}}"#, mch_id)))
+                .nonce(NONCE)
+                .associated_data(ASSOCIATED_DATA)
+                .algorithm("AEAD_AES_256_GCM")
+                .build()
+                .unwrap()
+        };
+
+        // 未知序列号时依次尝试各商户密钥
+        let (merchant, data) =
+            registry.route_notification::<serde_json::Value>(Some("UNKNOWN"), &resource(&key_1, "1900000001"))?;
+        assert_eq!(merchant.mch_id(), "1900000001");
+        assert_eq!(data["out_trade_no"], "T001");
+
+        // 按平台证书序列号路由
+        let (merchant, _) =
+            registry.route_notification::<serde_json::Value>(Some(&serial_no), &resource(&key_2, "1900000002"))?;
+        assert_eq!(merchant.mch_id(), "1900000002");
+
+        // 资源中的商户号未注册
+        assert!(registry
+            .route_notification::<serde_json::Value>(None, &resource(&key_2, "1900000009"))
+            .is_err());
+        Ok(())
+    }
+
+    /// 测试多个商户共用APIv3密钥时按解密后的商户号路由
+    #[test]
+    fn test_route_notification_shared_key() -> RPayResult<()> {
+        let key = "a".repeat(32);
+        let registry = MerchantRegistry::new();
+        registry.insert(config("1900000001", "wx_app_1", &key)?)?;
+        registry.insert(config("1900000002", "wx_app_2", &key)?)?;
+
+        for mch_id in ["1900000001", "1900000002"] {
+            let resource = ParseEncryptBuilder::default()
+                .ciphertext(encrypt(&key, &format!(r#"{{"mchid":"{}","out_trade_no":"T001"}}"#, mch_id)))
+                .nonce(NONCE)
+                .associated_data(ASSOCIATED_DATA)
+                .algorithm("AEAD_AES_256_GCM")
+                .build()?;
+            let (merchant, _) = registry.route_notification::<serde_json::Value>(None, &resource)?;
+            assert_eq!(merchant.mch_id(), mch_id);
+        }
+        Ok(())
+    }
+
+    /// 测试添加平台证书时解析证书，替换配置后保留已添加的证书
+    #[test]
+    fn test_add_platform_certificate() -> RPayResult<()> {
+        let registry = MerchantRegistry::new();
+        registry.insert(config("1900000001", "wx_app_1", "a".repeat(32).as_str())?)?;
+        // 证书格式错误时添加失败
+        assert!(registry.add_platform_certificate("1900000001", "invalid certificate").is_err());
+        let pem = std::fs::read(fixture("apiclient_cert.pem"))?;
+        // 商户未注册
+        assert!(registry.add_platform_certificate("1900000002", &pem).is_err());
+
+        let serial_no = registry.add_platform_certificate("1900000001", &pem)?;
+        let merchant = registry.get_by_serial_no(&serial_no).unwrap();
+        let certificate = &merchant.platform_certificates[&serial_no];
+        assert_eq!(certificate.serial_no, "4B3C1E2A6F3C9D1E8A7B6C5D4E3F2A1B0C9D8E7F");
+        assert!(certificate.expires_at > 0);
+
+        registry.insert(config("1900000001", "wx_app_1", "b".repeat(32).as_str())?)?;
+        let merchant = registry.get_by_mch_id("1900000001").unwrap();
+        assert!(merchant.platform_certificates.contains_key(&serial_no));
+        assert_eq!(merchant.config.api_key_v3.as_deref(), Some("b".repeat(32).as_str()));
+        Ok(())
+    }
+}
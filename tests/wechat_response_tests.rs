@@ -0,0 +1,41 @@
+#[cfg(test)]
+mod tests {
+
+    use bytes::Bytes;
+    use reqwest::{header::HeaderMap, StatusCode};
+    use rpay::ApiResponse;
+
+    fn response(headers: HeaderMap) -> ApiResponse<i64> {
+        ApiResponse {
+            status: StatusCode::OK,
+            headers,
+            body: 1,
+            raw: Bytes::from_static("{\"value\":1}".as_bytes()),
+        }
+    }
+
+    /// 测试从响应头读取Request-ID
+    #[test]
+    fn test_request_id() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Request-ID", "08F0A2C5B40610A10318D1D6E4A006-0".parse().unwrap());
+        assert_eq!(response(headers).request_id(), Some("08F0A2C5B40610A10318D1D6E4A006-0"));
+        assert_eq!(response(HeaderMap::new()).request_id(), None);
+    }
+
+    /// 测试原始响应体和转换响应数据
+    #[test]
+    fn test_raw_text_and_map() {
+        let mut headers = HeaderMap::new();
+        headers.insert("Request-ID", "REQUEST_ID".parse().unwrap());
+        let resp = response(headers);
+        assert_eq!(resp.raw_text(), r#"{"value":1}"#);
+
+        let resp = resp.map(|value| format!("value={}", value));
+        assert_eq!(resp.body, "value=1");
+        assert_eq!(resp.status, StatusCode::OK);
+        assert_eq!(resp.request_id(), Some("REQUEST_ID"));
+        assert_eq!(resp.raw_text(), r#"{"value":1}"#);
+        assert_eq!(resp.into_body(), "value=1");
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+
+    use dotenvy::dotenv;
+    use rpay::{
+        message::inbound::{xml_to_map, InboundEvent, InboundMessage},
+        security::{CheckScene, MediaCheckEvent, MsgSecCheckBuilder, SecurityCheckBuilder, Suggest},
+        RPayResult,
+    };
+
+    fn get_oepn_id() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_OPEN_ID").expect("WECHAT_OPEN_ID not found")
+    }
+
+    // 获取access_token，请通过auth模块获取,将其保存配置文，注意有效期2小时，不要频繁调用
+    fn get_access_token() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_ACCESS_TOKEN").expect("WECHAT_ACCESS_TOKEN not found")
+    }
+
+    /// 测试解析音视频检测结果推送
+    #[test]
+    fn test_media_check_event() -> RPayResult<()> {
+        let event: MediaCheckEvent = serde_json::from_str(
+            r#"{
+                "ToUserName": "gh_38cc49f9733b",
+                "FromUserName": "oH1fu0FdHqpToe2T6gBj0WyB8iS1",
+                "CreateTime": 1626959646,
+                "MsgType": "event",
+                "Event": "wxa_media_check",
+                "appid": "wx8f16a5e5f2c0d8a1",
+                "trace_id": "60f96f1d-3845297a-1976a3ae",
+                "version": 2,
+                "detail": [{"strategy": "content_model", "errcode": 0, "suggest": "pass", "label": 100, "prob": 90}],
+                "errcode": 0,
+                "errmsg": "ok",
+                "result": {"suggest": "pass", "label": 100}
+            }"#,
+        )?;
+        assert_eq!(event.header.create_time, 1626959646);
+        assert_eq!(event.result.suggest, Suggest::Pass);
+        assert_eq!(event.detail.len(), 1);
+        Ok(())
+    }
+
+    /// 测试解析XML格式的音视频内容安全识别结果推送
+    #[test]
+    fn test_media_check_event_xml() -> RPayResult<()> {
+        let detail = "<detail><strategy><![CDATA[content_model]]></strategy><errcode>0</errcode><suggest><![CDATA[pass]]></suggest><label>100</label><prob>90</prob></detail>";
+        let parse = |details: &str| {
+            InboundMessage::from_map(xml_to_map(&format!(
+                "<xml><ToUserName><![CDATA[gh_38cc49f9733b]]></ToUserName><FromUserName><![CDATA[oH1fu0FdHqpToe2T6gBj0WyB8iS1]]></FromUserName><CreateTime>1626959646</CreateTime><MsgType><![CDATA[event]]></MsgType><Event><![CDATA[wxa_media_check]]></Event><appid><![CDATA[wx8f16a5e5f2c0d8a1]]></appid><trace_id><![CDATA[60f96f1d-3845297a-1976a3ae]]></trace_id><version>2</version>{}<errcode>0</errcode><errmsg><![CDATA[ok]]></errmsg><result><suggest><![CDATA[pass]]></suggest><label>100</label></result></xml>",
+                details
+            ))?)
+        };
+
+        // 单个detail为对象
+        match parse(detail)? {
+            InboundMessage::Event(InboundEvent::WxaMediaCheck(event)) => {
+                assert_eq!(event.version, 2);
+                assert_eq!(event.result.label, 100);
+                assert_eq!(event.detail.len(), 1);
+                assert_eq!(event.detail[0].errcode, Some(0));
+                assert_eq!(event.detail[0].label, Some(100));
+                assert_eq!(event.detail[0].prob, Some(90));
+                assert_eq!(event.detail[0].suggest, Some(Suggest::Pass));
+            }
+            other => panic!("unexpected message: {:?}", other),
+        }
+        // 多个detail为数组
+        match parse(&format!("{}{}", detail, detail))? {
+            InboundMessage::Event(InboundEvent::WxaMediaCheck(event)) => assert_eq!(event.detail.len(), 2),
+            other => panic!("unexpected message: {:?}", other),
+        }
+        Ok(())
+    }
+
+    /// 文本内容安全识别
+    #[tokio::test]
+    async fn test_msg_sec_check() -> RPayResult<()> {
+        let params = MsgSecCheckBuilder::default()
+            .content("hello world")
+            .scene(CheckScene::Comment)
+            .openid(get_oepn_id())
+            .build()?;
+        let resp = SecurityCheckBuilder::default()
+            .access_token(get_access_token())
+            .build()?
+            .msg_sec_check(&params)
+            .await?;
+        println!("resp => {:?}", resp);
+        Ok(())
+    }
+}
@@ -0,0 +1,116 @@
+#[cfg(test)]
+mod tests {
+    use std::{collections::BTreeMap, sync::Arc};
+
+    use rpay::{
+        pay::{
+            config::{WechatV3PayConfig, WechatV3PayConfigBuilder},
+            signer::{v2_sign_content, HmacSha256Signer, Md5Signer, RsaSigner, SignAlgorithm, Signer},
+        },
+        utils, RPayResult,
+    };
+    use rsa::{
+        pkcs8::{EncodePrivateKey, LineEnding},
+        sha2::{Digest, Sha256},
+        Pkcs1v15Sign, RsaPrivateKey,
+    };
+
+    // 官方文档中的V2签名示例参数
+    fn v2_params() -> BTreeMap<String, String> {
+        let mut params = BTreeMap::new();
+        params.insert("appid".to_string(), "wxd930ea5d5a258f4f".to_string());
+        params.insert("mch_id".to_string(), "10000100".to_string());
+        params.insert("device_info".to_string(), "1000".to_string());
+        params.insert("body".to_string(), "test".to_string());
+        params.insert("nonce_str".to_string(), "ibuaiVcKdpRxkhJA".to_string());
+        params.insert("sign".to_string(), "".to_string());
+        params
+    }
+
+    /// 测试V2 MD5/HMAC-SHA256签名
+    #[test]
+    fn test_v2_sign() -> RPayResult<()> {
+        let content = v2_sign_content(&v2_params());
+        assert_eq!(
+            content,
+            "appid=wxd930ea5d5a258f4f&body=test&device_info=1000&mch_id=10000100&nonce_str=ibuaiVcKdpRxkhJA"
+        );
+        let key = "192006250b4c09247ec02edce69f6a2d";
+        let md5 = Md5Signer::new(key).sign(content.as_bytes())?;
+        assert_eq!(md5, "9A0A8659F005D6984697E2CA0A9CF3B7");
+        let hmac = HmacSha256Signer::new(key).sign(content.as_bytes())?;
+        assert_eq!(
+            hmac,
+            "6A9AE1657590FD6257D693A078E1C3E4BB6BA4DC30B23E0EE2496E54170DACD6"
+        );
+        Ok(())
+    }
+
+    /// 测试RSA签名，签名结果可以用公钥验证
+    #[test]
+    fn test_rsa_sign() -> RPayResult<()> {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+        let pem = private_key.to_pkcs8_pem(LineEnding::LF).unwrap();
+        let signer = RsaSigner::from_pem(pem.as_str())?;
+        assert_eq!(signer.algorithm(), SignAlgorithm::RsaSha256);
+        let content = "POST\n/v3/pay/transactions/jsapi\n1554208460\n593BEC0C930BF1AFEB40B4A08C8FB242\n{}\n";
+        let signature = utils::base64_decode(signer.sign(content.as_bytes())?)?;
+        let hashed = Sha256::new().chain_update(content).finalize();
+        assert!(private_key
+            .to_public_key()
+            .verify(Pkcs1v15Sign::new::<Sha256>(), &hashed, &signature)
+            .is_ok());
+        Ok(())
+    }
+
+    /// 测试配置中的私钥只解析一次
+    #[test]
+    fn test_cached_signer() -> RPayResult<()> {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+        let pem = private_key.to_pkcs8_pem(LineEnding::LF).unwrap();
+        assert_eq!(
+            utils::sha256_sign(pem.to_string(), "content".to_string())?,
+            RsaSigner::from_pem(pem.as_str())?.sign(b"content")?
+        );
+
+        // 直接反序列化的配置没有签名器，多次获取时复用同一个签名器
+        let config: WechatV3PayConfig = serde_json::from_value(serde_json::json!({
+            "app_id": "wx_app_id",
+            "secret": "secret",
+            "mch_id": "1900000001",
+            "serial_no": "SERIAL_NO",
+            "private_key": pem.as_str(),
+        }))?;
+        assert!(config.signer.is_none());
+        let signer = config.signer()?;
+        assert!(Arc::ptr_eq(&signer, &config.signer()?));
+        // clone后的配置共享已解析的签名器
+        assert!(Arc::ptr_eq(&signer, &config.clone().signer()?));
+        Ok(())
+    }
+
+    /// 测试构建配置时解析私钥
+    #[test]
+    fn test_config_signer() -> RPayResult<()> {
+        let private_key = RsaPrivateKey::new(&mut rand::thread_rng(), 1024).unwrap();
+        let pem = private_key.to_pkcs8_pem(LineEnding::LF).unwrap();
+        let config = WechatV3PayConfigBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .mch_id("1900000001")
+            .serial_no("SERIAL_NO")
+            .private_key(pem.as_str())
+            .build()?;
+        assert!(config.signer.is_some());
+        // 私钥格式错误时构建失败
+        let result = WechatV3PayConfigBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .mch_id("1900000001")
+            .serial_no("SERIAL_NO")
+            .private_key("invalid private key")
+            .build();
+        assert!(result.is_err());
+        Ok(())
+    }
+}
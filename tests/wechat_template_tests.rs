@@ -2,7 +2,12 @@
 mod tests {
 
     use dotenvy::dotenv;
-    use rpay::{pay::config::{WechatV3PayConfig, WechatV3PayConfigBuilder}, template::TemplatesBuilder, RPayError, RPayResult};
+    use rpay::{
+        message::template_message::{TemplateData, TemplateMessageBuilder, TemplateMiniProgram},
+        pay::config::WechatV3PayConfig,
+        template::{official::OfficialTemplatesBuilder, parse_keywords, PubTemplateTitlesBuilder, TemplatesBuilder},
+        RPayError, RPayResult,
+    };
 
     #[allow(unused)]
     fn get_oepn_id() -> String {
@@ -22,24 +27,7 @@ mod tests {
     #[allow(unused)]
     fn get_sdk() -> RPayResult<WechatV3PayConfig> {
         dotenv().ok();
-        let app_id = std::env::var("WECHAT_APP_ID").expect("WECHAT_APP_ID not found");
-        let mch_id = std::env::var("WECHAT_MCH_ID").expect("WECHAT_MCH_ID not found");
-        let secret = std::env::var("WECHAT_SECRET").expect("WECHAT_SECRET not found");
-        let private_key =
-            std::env::var("WECHAT_PRIVATE_KEY").expect("WECHAT_PRIVATE_KEY not found");
-        let serial_no = std::env::var("WECHAT_SERIAL_NO").expect("WECHAT_SERIAL_NO not found");
-        let v3_key = std::env::var("WECHAT_KEY_V3").expect("WECHAT_KEY_V3 not found");
-        let notify_url = std::env::var("WECHAT_NOTIFY_URL").expect("WECHAT_NOTIFY_URL not found");
-        let sdk = WechatV3PayConfigBuilder::default()
-        .app_id(app_id)
-        .secret(secret)
-        .api_key_v3(v3_key)
-        .serial_no(serial_no.to_string())
-        .mch_id(mch_id)
-        .private_key(private_key)
-        .notify_url(notify_url)
-        .build()?;
-        Ok(sdk)
+        WechatV3PayConfig::from_env("WECHAT")
     }
 
     // 获取行业分类
@@ -66,5 +54,89 @@ mod tests {
         Ok(())
     }
 
+    // 获取公共模板标题
+    #[tokio::test]
+    async fn test_get_pub_template_titles() -> RPayResult<()> {
+        let params = PubTemplateTitlesBuilder::default().ids(vec![2, 616]).limit(10).build()?;
+        let resp = TemplatesBuilder::default()
+            .access_token(get_access_token())
+            .build()?
+            .get_pub_template_titles(&params)
+            .await?;
+        println!("resp => {:?}", resp);
+        Ok(())
+    }
 
+    // 获取个人模板列表
+    #[tokio::test]
+    async fn test_get_template() -> RPayResult<()> {
+        let resp = TemplatesBuilder::default()
+            .access_token(get_access_token())
+            .build()?
+            .get_template()
+            .await?;
+        for template in resp.data.unwrap_or_default() {
+            println!("{} => {:?}", template.pri_tmpl_id, template.keywords());
+        }
+        Ok(())
+    }
+
+    /// 测试解析模板内容中的关键词
+    #[test]
+    fn test_parse_keywords() {
+        let content = "商品名称:{{thing1.DATA}}\n商品金额:{{amount2.DATA}}\n下单时间:{{ time4.DATA }}\n";
+        assert_eq!(parse_keywords(content), vec!["thing1", "amount2", "time4"]);
+        assert!(parse_keywords("{{thing1.DATA").is_empty());
+    }
+
+    /// 测试公众号模板消息参数
+    #[test]
+    fn test_template_message_params() -> RPayResult<()> {
+        let message = TemplateMessageBuilder::default()
+            .access_token("token")
+            .touser("oUser")
+            .template_id("ngqIpbwh8bUfcSsECmogfXcV14J0tQlEpBO27izEYtY")
+            .url("https://example.com")
+            .miniprogram(TemplateMiniProgram {
+                appid: String::from("wx_appid"),
+                pagepath: Some(String::from("index?foo=bar")),
+            })
+            .client_msg_id("order_1")
+            .item("keyword1", TemplateData::new("巧克力").color("#173177"))
+            .item("keyword2", TemplateData::new("39.8元"))
+            .build()?;
+        let body = serde_json::to_value(&message)?;
+        assert!(body.get("access_token").is_none());
+        assert_eq!(body["miniprogram"]["pagepath"], "index?foo=bar");
+        assert_eq!(body["client_msg_id"], "order_1");
+        assert_eq!(body["data"]["keyword1"]["color"], "#173177");
+        assert!(body["data"]["keyword2"].get("color").is_none());
+        Ok(())
+    }
+
+    // 发送公众号模板消息
+    #[tokio::test]
+    async fn test_template_message_send() -> RPayResult<()> {
+        let resp = TemplateMessageBuilder::default()
+            .access_token(get_access_token())
+            .touser(get_oepn_id())
+            .template_id("ngqIpbwh8bUfcSsECmogfXcV14J0tQlEpBO27izEYtY")
+            .item("keyword1", TemplateData::new("巧克力"))
+            .build()?
+            .send()
+            .await?;
+        println!("resp => {:?}", resp);
+        Ok(())
+    }
+
+    // 获取公众号所属行业和模板列表
+    #[tokio::test]
+    async fn test_official_templates() -> RPayResult<()> {
+        let templates = OfficialTemplatesBuilder::default()
+            .access_token(get_access_token())
+            .build()?;
+        println!("industry => {:?}", templates.get_industry().await?);
+        println!("templates => {:?}", templates.get_all_private_template().await?);
+        Ok(())
+    }
 }
@@ -0,0 +1,133 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    };
+
+    use rpay::{
+        auth::{
+            token_manager::AccessTokenManagerBuilder,
+            token_store::{CachedToken, MemoryTokenStore, TokenStore},
+        },
+        ApiProvider, RPayError, RPayResult,
+    };
+
+    // 删除令牌时模拟其他实例已刷新，写入新令牌，避免测试中请求微信接口
+    #[derive(Debug, Default)]
+    struct RefreshedStore {
+        inner: MemoryTokenStore,
+    }
+
+    impl TokenStore for RefreshedStore {
+        fn get(&self, key: &str) -> RPayResult<Option<CachedToken>> {
+            self.inner.get(key)
+        }
+
+        fn set(&self, key: &str, token: &CachedToken) -> RPayResult<()> {
+            self.inner.set(key, token)
+        }
+
+        fn remove(&self, key: &str) -> RPayResult<()> {
+            self.inner.set(key, &CachedToken::new("refreshed", 7200))
+        }
+
+        fn try_lock(&self, key: &str, value: &str, ttl: u64) -> RPayResult<bool> {
+            self.inner.try_lock(key, value, ttl)
+        }
+
+        fn unlock(&self, key: &str, value: &str) -> RPayResult<()> {
+            self.inner.unlock(key, value)
+        }
+    }
+
+    /// 测试内存存储的令牌过期和刷新锁
+    #[test]
+    fn test_memory_store() -> RPayResult<()> {
+        let store = MemoryTokenStore::new();
+        store.set("token", &CachedToken::new("expired", 0))?;
+        assert!(store.get("token")?.is_none());
+        store.set("token", &CachedToken::new("valid", 7200))?;
+        assert_eq!(store.get("token")?.unwrap().access_token, "valid");
+
+        assert!(store.try_lock("lock", "a", 30)?);
+        assert!(!store.try_lock("lock", "b", 30)?);
+        // 只能释放自己持有的锁
+        store.unlock("lock", "b")?;
+        assert!(!store.try_lock("lock", "b", 30)?);
+        store.unlock("lock", "a")?;
+        assert!(store.try_lock("lock", "b", 30)?);
+        Ok(())
+    }
+
+    /// 测试使用缓存令牌，以及令牌失效
+    #[tokio::test]
+    async fn test_manager_cached_token() -> RPayResult<()> {
+        let store = Arc::new(MemoryTokenStore::new());
+        store.set("rpay:access_token:wx_app_id", &CachedToken::new("cached", 7200))?;
+        let manager = AccessTokenManagerBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .store(store.clone())
+            .build()?;
+        assert_eq!(manager.get_token().await?, "cached");
+
+        // 其他调用已刷新令牌时不删除
+        manager.invalidate("other")?;
+        assert!(store.get("rpay:access_token:wx_app_id")?.is_some());
+        manager.invalidate("cached")?;
+        assert!(store.get("rpay:access_token:wx_app_id")?.is_none());
+        Ok(())
+    }
+
+    /// 测试其他实例持有刷新锁时，在提前刷新期内继续使用旧令牌
+    #[tokio::test]
+    async fn test_manager_refresh_locked() -> RPayResult<()> {
+        let store = Arc::new(MemoryTokenStore::new());
+        store.set("rpay:access_token:wx_app_id", &CachedToken::new("stale", 60))?;
+        assert!(store.try_lock("rpay:access_token:wx_app_id:lock", "other", 30)?);
+        let manager = AccessTokenManagerBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .store(store)
+            .build()?;
+        assert_eq!(manager.get_token().await?, "stale");
+        Ok(())
+    }
+
+    /// 测试接口返回40001时使令牌失效并使用新令牌重试一次
+    #[tokio::test]
+    async fn test_manager_call_retry() -> RPayResult<()> {
+        let store = Arc::new(RefreshedStore::default());
+        store.set("rpay:access_token:wx_app_id", &CachedToken::new("expired", 7200))?;
+        let manager = AccessTokenManagerBuilder::default()
+            .app_id("wx_app_id")
+            .secret("secret")
+            .store(store)
+            .build()?;
+
+        let calls = AtomicU32::new(0);
+        let token = manager
+            .call(|access_token| {
+                let calls = &calls;
+                async move {
+                    calls.fetch_add(1, Ordering::SeqCst);
+                    match access_token.as_str() {
+                        "expired" => Err(RPayError::Api {
+                            provider: ApiProvider::Wechat,
+                            http_status: 200,
+                            code: "40001".to_string(),
+                            message: "invalid credential".to_string(),
+                            request_id: None,
+                            detail: None,
+                        }),
+                        _ => Ok(access_token),
+                    }
+                }
+            })
+            .await?;
+        assert_eq!(token, "refreshed");
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+#[cfg(test)]
+mod tests {
+
+    use dotenvy::dotenv;
+    use rpay::{
+        wxa::{
+            link::{GenerateSchemeBuilder, GenerateUrlLinkBuilder, JumpWxaBuilder, LinkExpire, WxaLinkBuilder},
+            wxacode::{EnvVersion, GetUnlimitedWxaCodeBuilder, LineColor, WxaCodeBuilder},
+        },
+        RPayResult,
+    };
+
+    // 获取access_token，请通过auth模块获取,将其保存配置文，注意有效期2小时，不要频繁调用
+    fn get_access_token() -> String {
+        dotenv().ok();
+        std::env::var("WECHAT_ACCESS_TOKEN").expect("WECHAT_ACCESS_TOKEN not found")
+    }
+
+    /// 测试小程序码参数序列化，未设置的参数不提交
+    #[test]
+    fn test_unlimited_params() -> RPayResult<()> {
+        let params = GetUnlimitedWxaCodeBuilder::default()
+            .scene("store=1001")
+            .page("pages/index/index")
+            .env_version(EnvVersion::Trial)
+            .line_color(LineColor { r: 0, g: 0, b: 0 })
+            .build()?;
+        assert_eq!(
+            serde_json::to_value(&params)?,
+            serde_json::json!({
+                "scene": "store=1001",
+                "page": "pages/index/index",
+                "env_version": "trial",
+                "line_color": {"r": 0, "g": 0, "b": 0}
+            })
+        );
+        Ok(())
+    }
+
+    /// 获取不限制的小程序码，保存为图片
+    #[tokio::test]
+    async fn test_get_unlimited() -> RPayResult<()> {
+        let params = GetUnlimitedWxaCodeBuilder::default()
+            .scene("store=1001")
+            .check_path(false)
+            .build()?;
+        let image = WxaCodeBuilder::default()
+            .access_token(get_access_token())
+            .build()?
+            .get_unlimited(&params)
+            .await?;
+        std::fs::write(std::env::temp_dir().join("wxacode.png"), image)?;
+        Ok(())
+    }
+
+    /// 测试链接失效方式序列化
+    #[test]
+    fn test_link_params() -> RPayResult<()> {
+        let params = GenerateSchemeBuilder::default()
+            .jump_wxa(JumpWxaBuilder::default().path("pages/index/index").query("a=1").build()?)
+            .expire(LinkExpire::Interval(7))
+            .build()?;
+        assert_eq!(
+            serde_json::to_value(&params)?,
+            serde_json::json!({
+                "jump_wxa": {"path": "pages/index/index", "query": "a=1"},
+                "expire_type": 1,
+                "expire_interval": 7
+            })
+        );
+        let params = GenerateUrlLinkBuilder::default()
+            .env_version(EnvVersion::Develop)
+            .expire(LinkExpire::At(1700000000))
+            .build()?;
+        assert_eq!(
+            serde_json::to_value(&params)?,
+            serde_json::json!({"env_version": "develop", "expire_type": 0, "expire_time": 1700000000})
+        );
+        Ok(())
+    }
+
+    /// 生成URL Link并查询
+    #[tokio::test]
+    async fn test_url_link() -> RPayResult<()> {
+        let link = WxaLinkBuilder::default().access_token(get_access_token()).build()?;
+        let params = GenerateUrlLinkBuilder::default()
+            .expire(LinkExpire::Interval(1))
+            .build()?;
+        let resp = link.generate_url_link(&params).await?;
+        println!("resp => {:?}", resp);
+        let info = link.query_url_link(&resp.url_link.unwrap_or_default()).await?;
+        println!("info => {:?}", info);
+        Ok(())
+    }
+}